@@ -0,0 +1,317 @@
+use crate::graph::Edge;
+
+/// (from, to) of an input edge
+type Arc = (usize, usize);
+
+/// Edge of the input with its key shifted by the lazy additions of the heap
+#[derive(Clone, Copy, Debug)]
+struct HeapNode {
+    from: usize,
+    to: usize,
+    key: isize,
+    /// addition not yet applied to this node and its children
+    lazy: isize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// Skew heaps with lazy addition, allocated in one arena
+struct SkewHeaps {
+    nodes: Vec<HeapNode>,
+}
+
+impl SkewHeaps {
+    fn push_down(&mut self, x: usize) {
+        let lazy = self.nodes[x].lazy;
+        if lazy == 0 {
+            return;
+        }
+        self.nodes[x].key += lazy;
+        self.nodes[x].lazy = 0;
+        for child in [self.nodes[x].left, self.nodes[x].right]
+            .into_iter()
+            .flatten()
+        {
+            self.nodes[child].lazy += lazy;
+        }
+    }
+
+    /// Merge two heaps along their right spines
+    fn merge(&mut self, mut a: Option<usize>, mut b: Option<usize>) -> Option<usize> {
+        let mut path = vec![];
+        while let (Some(mut x), Some(mut y)) = (a, b) {
+            self.push_down(x);
+            self.push_down(y);
+            if self.nodes[x].key > self.nodes[y].key {
+                std::mem::swap(&mut x, &mut y);
+            }
+            path.push(x);
+            a = self.nodes[x].right;
+            b = Some(y);
+        }
+        let mut tail = a.or(b);
+        for &x in path.iter().rev() {
+            self.nodes[x].right = self.nodes[x].left;
+            self.nodes[x].left = tail;
+            tail = Some(x);
+        }
+        tail
+    }
+
+    fn pop(&mut self, x: usize) -> Option<usize> {
+        self.push_down(x);
+        self.merge(self.nodes[x].left, self.nodes[x].right)
+    }
+}
+
+/// Union find with rollback (union by size, no path compression)
+struct RollbackUnionFind {
+    /// parent, or negative size for roots
+    parents: Vec<isize>,
+    history: Vec<(usize, isize)>,
+}
+
+impl RollbackUnionFind {
+    fn root(&self, mut x: usize) -> usize {
+        while self.parents[x] >= 0 {
+            x = self.parents[x] as usize;
+        }
+        x
+    }
+
+    fn unite(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.root(x), self.root(y));
+        if x == y {
+            return false;
+        }
+        if self.parents[x] > self.parents[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.history.push((x, self.parents[x]));
+        self.history.push((y, self.parents[y]));
+        self.parents[x] += self.parents[y];
+        self.parents[y] = x as isize;
+        true
+    }
+
+    fn rollback(&mut self, time: usize) {
+        while self.history.len() > time {
+            let (x, value) = self.history.pop().unwrap();
+            self.parents[x] = value;
+        }
+    }
+}
+
+/// Minimum spanning arborescence (directed MST) rooted at root
+/// by Chu-Liu/Edmonds with skew heaps in O(E log V).
+/// Returns the total cost and the parent of every vertex (None for the root),
+/// or None if some vertex is unreachable from root.
+/// ```
+/// use competitive_tools_rust::arborescence::minimum_spanning_arborescence;
+/// use competitive_tools_rust::graph::Edge;
+/// // 0 -> 1 (10), 0 -> 2 (1), 2 -> 1 (2), 1 -> 3 (3), 3 -> 2 (1)
+/// let edges_list = vec![
+///     vec![Edge { to: 1, cost: 10 }, Edge { to: 2, cost: 1 }],
+///     vec![Edge { to: 3, cost: 3 }],
+///     vec![Edge { to: 1, cost: 2 }],
+///     vec![Edge { to: 2, cost: 1 }],
+/// ];
+/// assert_eq!(
+///     minimum_spanning_arborescence(0, &edges_list),
+///     Some((6, vec![None, Some(2), Some(0), Some(1)]))
+/// );
+/// assert_eq!(minimum_spanning_arborescence(3, &edges_list), None);
+/// ```
+pub fn minimum_spanning_arborescence(
+    root: usize,
+    edges_list: &[Vec<Edge>],
+) -> Option<(usize, Vec<Option<usize>>)> {
+    let n = edges_list.len();
+    let mut heaps = SkewHeaps { nodes: vec![] };
+    let mut heap: Vec<Option<usize>> = vec![None; n];
+    for (from, edges) in edges_list.iter().enumerate() {
+        for e in edges {
+            let id = heaps.nodes.len();
+            heaps.nodes.push(HeapNode {
+                from,
+                to: e.to,
+                key: e.cost as isize,
+                lazy: 0,
+                left: None,
+                right: None,
+            });
+            heap[e.to] = heaps.merge(heap[e.to], Some(id));
+        }
+    }
+
+    let mut union_find = RollbackUnionFind {
+        parents: vec![-1; n],
+        history: vec![],
+    };
+    let mut cost: isize = 0;
+    let mut seen: Vec<Option<usize>> = vec![None; n];
+    seen[root] = Some(root);
+    // chosen incoming edge of each contracted vertex
+    let mut incoming: Vec<Option<Arc>> = vec![None; n];
+    // (contracted vertex, time of union find, edges of the cycle)
+    let mut cycles: Vec<(usize, usize, Vec<Arc>)> = vec![];
+    let mut path: Vec<(usize, Arc)> = vec![];
+    for start in 0..n {
+        let mut u = start;
+        path.clear();
+        while seen[u].is_none() {
+            let top = heap[u]?;
+            heaps.push_down(top);
+            let HeapNode { from, to, key, .. } = heaps.nodes[top];
+            heaps.nodes[top].lazy -= key;
+            heap[u] = heaps.pop(top);
+            path.push((u, (from, to)));
+            seen[u] = Some(start);
+            cost += key;
+            u = union_find.root(from);
+            if seen[u] == Some(start) {
+                // found a cycle, contract it into one vertex
+                let time = union_find.history.len();
+                let mut merged = None;
+                let mut cycle = vec![];
+                loop {
+                    let (w, e) = path.pop().unwrap();
+                    merged = heaps.merge(merged, heap[w]);
+                    cycle.push(e);
+                    if !union_find.unite(u, w) {
+                        break;
+                    }
+                }
+                u = union_find.root(u);
+                heap[u] = merged;
+                seen[u] = None;
+                cycles.push((u, time, cycle));
+            }
+        }
+        for &(_, (from, to)) in &path {
+            incoming[union_find.root(to)] = Some((from, to));
+        }
+    }
+    // expand the cycles in the reverse order of contraction
+    for (u, time, cycle) in cycles.into_iter().rev() {
+        union_find.rollback(time);
+        let entering = incoming[u];
+        for &(from, to) in &cycle {
+            incoming[union_find.root(to)] = Some((from, to));
+        }
+        if let Some((from, to)) = entering {
+            incoming[union_find.root(to)] = Some((from, to));
+        }
+    }
+    let parent = (0..n)
+        .map(|v| {
+            if v == root {
+                None
+            } else {
+                incoming[v].map(|(from, _)| from)
+            }
+        })
+        .collect();
+    Some((cost as usize, parent))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arborescence::minimum_spanning_arborescence;
+    use crate::graph::Edge;
+    use crate::test_util::Rng;
+
+    fn reaches_root(parent: &[Option<usize>], root: usize) -> bool {
+        let n = parent.len();
+        (0..n).all(|v| {
+            let mut u = v;
+            for _ in 0..n {
+                if u == root {
+                    return true;
+                }
+                match parent[u] {
+                    Some(p) => u = p,
+                    None => return false,
+                }
+            }
+            u == root
+        })
+    }
+
+    #[test]
+    fn test_with_naive() {
+        let mut rng = Rng::new(53);
+        for _ in 0..300 {
+            let n = 1 + rng.rand(6);
+            let root = rng.rand(n);
+            let mut edges_list: Vec<Vec<Edge>> = vec![vec![]; n];
+            for _ in 0..rng.rand(3 * n) {
+                let (u, v) = (rng.rand(n), rng.rand(n));
+                edges_list[u].push(Edge {
+                    to: v,
+                    cost: rng.rand(10),
+                });
+            }
+            // cheapest edge u -> v
+            let mut cheapest: Vec<Vec<Option<usize>>> = vec![vec![None; n]; n];
+            for (u, edges) in edges_list.iter().enumerate() {
+                for e in edges {
+                    if cheapest[u][e.to].is_none_or(|c| e.cost < c) {
+                        cheapest[u][e.to] = Some(e.cost);
+                    }
+                }
+            }
+            // try every choice of parents
+            let mut naive = None;
+            for code in 0..n.pow(n as u32) {
+                let parent: Vec<Option<usize>> = (0..n)
+                    .map(|v| {
+                        if v == root {
+                            None
+                        } else {
+                            Some(code / n.pow(v as u32) % n)
+                        }
+                    })
+                    .collect();
+                let total: Option<usize> = (0..n)
+                    .filter(|&v| v != root)
+                    .map(|v| cheapest[parent[v].unwrap()][v])
+                    .sum();
+                if let Some(total) = total
+                    && reaches_root(&parent, root)
+                    && naive.is_none_or(|best| total < best)
+                {
+                    naive = Some(total);
+                }
+            }
+
+            let result = minimum_spanning_arborescence(root, &edges_list);
+            assert_eq!(result.as_ref().map(|r| r.0), naive);
+            if let Some((cost, parent)) = result {
+                assert!(reaches_root(&parent, root));
+                let total: Option<usize> = (0..n)
+                    .filter(|&v| v != root)
+                    .map(|v| cheapest[parent[v].unwrap()][v])
+                    .sum();
+                assert_eq!(total, Some(cost));
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_cycle() {
+        let n = 100_000;
+        // 0 -> 1 -> ... -> n - 1 -> 1 with a cheap cycle and expensive entrances
+        let mut edges_list: Vec<Vec<Edge>> = vec![vec![]; n];
+        for v in 1..n {
+            edges_list[v].push(Edge {
+                to: if v + 1 < n { v + 1 } else { 1 },
+                cost: 1,
+            });
+            edges_list[0].push(Edge { to: v, cost: 100 });
+        }
+        let (cost, parent) = minimum_spanning_arborescence(0, &edges_list).unwrap();
+        assert_eq!(cost, 100 + n - 2);
+        assert_eq!(parent.iter().filter(|&&p| p == Some(0)).count(), 1);
+    }
+}
@@ -0,0 +1,373 @@
+use std::collections::VecDeque;
+
+/// Maximum bipartite matching by Hopcroft-Karp in O(E sqrt(V))
+/// Left vertexes are 0..left and right vertexes are 0..right
+pub struct HopcroftKarp {
+    graph: Vec<Vec<usize>>,
+    match_left: Vec<Option<usize>>,
+    match_right: Vec<Option<usize>>,
+}
+
+impl HopcroftKarp {
+    pub fn new(left: usize, right: usize) -> Self {
+        HopcroftKarp {
+            graph: vec![vec![]; left],
+            match_left: vec![None; left],
+            match_right: vec![None; right],
+        }
+    }
+
+    /// Add edge between left vertex l and right vertex r
+    pub fn add_edge(&mut self, l: usize, r: usize) {
+        self.graph[l].push(r);
+    }
+
+    /// Pairs of (left, right) in a maximum matching
+    /// ```
+    /// use competitive_tools_rust::bipartite_matching::HopcroftKarp;
+    /// // 0 - 0
+    /// //   X
+    /// // 1   1
+    /// //
+    /// // 2
+    /// let mut hk = HopcroftKarp::new(3, 2);
+    /// hk.add_edge(0, 0);
+    /// hk.add_edge(0, 1);
+    /// hk.add_edge(1, 0);
+    /// assert_eq!(hk.maximum_matching(), vec![(0, 1), (1, 0)]);
+    /// ```
+    pub fn maximum_matching(&mut self) -> Vec<(usize, usize)> {
+        let left = self.graph.len();
+        loop {
+            let dist = self.bfs_levels();
+            let mut dist = match dist {
+                Some(dist) => dist,
+                None => break,
+            };
+            let mut iter = vec![0; left];
+            let mut augmented = false;
+            for l in 0..left {
+                if self.match_left[l].is_none() && self.augment(l, &mut dist, &mut iter) {
+                    augmented = true;
+                }
+            }
+            if !augmented {
+                break;
+            }
+        }
+        self.match_left
+            .iter()
+            .enumerate()
+            .filter_map(|(l, r)| r.map(|r| (l, r)))
+            .collect()
+    }
+
+    /// Minimum vertex cover as (left vertexes, right vertexes) by König's theorem
+    /// ```
+    /// use competitive_tools_rust::bipartite_matching::HopcroftKarp;
+    /// // left 0, 1, 2 are connected to right 0, and left 2 is also connected to right 1
+    /// let mut hk = HopcroftKarp::new(3, 2);
+    /// hk.add_edge(0, 0);
+    /// hk.add_edge(1, 0);
+    /// hk.add_edge(2, 0);
+    /// hk.add_edge(2, 1);
+    /// assert_eq!(hk.minimum_vertex_cover(), (vec![2], vec![0]));
+    /// ```
+    pub fn minimum_vertex_cover(&mut self) -> (Vec<usize>, Vec<usize>) {
+        let (reached_left, reached_right) = self.alternating_reachable();
+        (
+            (0..self.match_left.len())
+                .filter(|&l| !reached_left[l])
+                .collect(),
+            (0..self.match_right.len())
+                .filter(|&r| reached_right[r])
+                .collect(),
+        )
+    }
+
+    /// Maximum independent set as (left vertexes, right vertexes),
+    /// which is the complement of the minimum vertex cover
+    /// ```
+    /// use competitive_tools_rust::bipartite_matching::HopcroftKarp;
+    /// let mut hk = HopcroftKarp::new(3, 2);
+    /// hk.add_edge(0, 0);
+    /// hk.add_edge(1, 0);
+    /// hk.add_edge(2, 0);
+    /// hk.add_edge(2, 1);
+    /// assert_eq!(hk.maximum_independent_set(), (vec![0, 1], vec![1]));
+    /// ```
+    pub fn maximum_independent_set(&mut self) -> (Vec<usize>, Vec<usize>) {
+        let (reached_left, reached_right) = self.alternating_reachable();
+        (
+            (0..self.match_left.len())
+                .filter(|&l| reached_left[l])
+                .collect(),
+            (0..self.match_right.len())
+                .filter(|&r| !reached_right[r])
+                .collect(),
+        )
+    }
+
+    /// Vertexes reachable from free left vertexes by alternating paths
+    /// after computing a maximum matching
+    fn alternating_reachable(&mut self) -> (Vec<bool>, Vec<bool>) {
+        self.maximum_matching();
+        let mut reached_left = vec![false; self.match_left.len()];
+        let mut reached_right = vec![false; self.match_right.len()];
+        let mut stack: Vec<usize> = (0..self.match_left.len())
+            .filter(|&l| self.match_left[l].is_none())
+            .collect();
+        stack.iter().for_each(|&l| reached_left[l] = true);
+        while let Some(l) = stack.pop() {
+            for &r in &self.graph[l] {
+                if reached_right[r] {
+                    continue;
+                }
+                reached_right[r] = true;
+                if let Some(next) = self.match_right[r]
+                    && !reached_left[next]
+                {
+                    reached_left[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        (reached_left, reached_right)
+    }
+
+    /// Distances of left vertexes in alternating BFS from free left vertexes,
+    /// or None when no augmenting path exists
+    fn bfs_levels(&self) -> Option<Vec<usize>> {
+        let mut dist = vec![usize::MAX; self.graph.len()];
+        let mut que = VecDeque::new();
+        for (l, m) in self.match_left.iter().enumerate() {
+            if m.is_none() {
+                dist[l] = 0;
+                que.push_back(l);
+            }
+        }
+        let mut found = false;
+        while let Some(l) = que.pop_front() {
+            for &r in &self.graph[l] {
+                match self.match_right[r] {
+                    None => found = true,
+                    Some(next) => {
+                        if dist[next] == usize::MAX {
+                            dist[next] = dist[l] + 1;
+                            que.push_back(next);
+                        }
+                    }
+                }
+            }
+        }
+        if found { Some(dist) } else { None }
+    }
+
+    /// Iterative DFS for an augmenting path from free left vertex start along levels
+    fn augment(&mut self, start: usize, dist: &mut [usize], iter: &mut [usize]) -> bool {
+        // lefts on the current path, and rights between them
+        let mut lefts = vec![start];
+        let mut rights: Vec<usize> = vec![];
+        while let Some(&l) = lefts.last() {
+            if iter[l] == self.graph[l].len() {
+                // dead end
+                dist[l] = usize::MAX;
+                lefts.pop();
+                rights.pop();
+                continue;
+            }
+            let r = self.graph[l][iter[l]];
+            iter[l] += 1;
+            match self.match_right[r] {
+                None => {
+                    rights.push(r);
+                    for (&l, &r) in lefts.iter().zip(rights.iter()) {
+                        self.match_left[l] = Some(r);
+                        self.match_right[r] = Some(l);
+                    }
+                    return true;
+                }
+                Some(next) => {
+                    if dist[next] == dist[l] + 1 {
+                        lefts.push(next);
+                        rights.push(r);
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+/// Minimum weight perfect matching by Hungarian algorithm in O(n^2 m)
+/// cost\[i\]\[j\] is the cost to assign row i to column j,
+/// and the number of rows must not exceed the number of columns.
+/// Returns (minimum cost, column assigned to each row)
+/// ```
+/// use competitive_tools_rust::bipartite_matching::hungarian;
+/// let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+/// assert_eq!(hungarian(&cost), (5, vec![1, 0, 2]));
+/// // maximize by negating costs
+/// assert_eq!(hungarian(&[vec![-7, -5, -1], vec![-3, -6, -9]]), (-16, vec![0, 2]));
+/// ```
+pub fn hungarian(cost: &[Vec<isize>]) -> (isize, Vec<usize>) {
+    let n = cost.len();
+    if n == 0 {
+        return (0, vec![]);
+    }
+    let m = cost[0].len();
+    assert!(n <= m);
+    // potentials of rows (1-indexed) and columns (1-indexed, 0 is a sentinel)
+    let mut u = vec![0isize; n + 1];
+    let mut v = vec![0isize; m + 1];
+    // row matched to each column
+    let mut p = vec![0usize; m + 1];
+    let mut way = vec![0usize; m + 1];
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut min_v = vec![isize::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = isize::MAX;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if cur < min_v[j] {
+                    min_v[j] = cur;
+                    way[j] = j0;
+                }
+                if min_v[j] < delta {
+                    delta = min_v[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_v[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+    let mut assignment = vec![0; n];
+    for j in 1..=m {
+        if p[j] != 0 {
+            assignment[p[j] - 1] = j - 1;
+        }
+    }
+    let total = (0..n).map(|i| cost[i][assignment[i]]).sum();
+    (total, assignment)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bipartite_matching::{HopcroftKarp, hungarian};
+    use crate::test_util::Rng;
+    use itertools::Itertools;
+
+    fn naive_maximum_matching(left: usize, right: usize, edges: &[(usize, usize)]) -> usize {
+        (0..1usize << edges.len())
+            .filter_map(|mask| {
+                let chosen: Vec<&(usize, usize)> = edges
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| (mask >> i) & 1 == 1)
+                    .map(|(_, e)| e)
+                    .collect();
+                let ls = chosen.iter().map(|e| e.0).unique().count();
+                let rs = chosen.iter().map(|e| e.1).unique().count();
+                if ls == chosen.len() && rs == chosen.len() && ls <= left && rs <= right {
+                    Some(chosen.len())
+                } else {
+                    None
+                }
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_matching_cover_and_independent_set() {
+        let mut rng = Rng::new(5);
+        for _ in 0..200 {
+            let left = 1 + rng.rand(5);
+            let right = 1 + rng.rand(5);
+            let edges: Vec<(usize, usize)> = (0..rng.rand(10))
+                .map(|_| (rng.rand(left), rng.rand(right)))
+                .unique()
+                .collect();
+            let mut hk = HopcroftKarp::new(left, right);
+            for &(l, r) in &edges {
+                hk.add_edge(l, r);
+            }
+            let matching = hk.maximum_matching();
+            assert_eq!(matching.len(), naive_maximum_matching(left, right, &edges));
+            for &(l, r) in &matching {
+                assert!(edges.contains(&(l, r)));
+            }
+            assert_eq!(
+                matching.iter().map(|p| p.0).unique().count(),
+                matching.len()
+            );
+            assert_eq!(
+                matching.iter().map(|p| p.1).unique().count(),
+                matching.len()
+            );
+
+            let (cover_left, cover_right) = hk.minimum_vertex_cover();
+            assert_eq!(cover_left.len() + cover_right.len(), matching.len());
+            for &(l, r) in &edges {
+                assert!(cover_left.contains(&l) || cover_right.contains(&r));
+            }
+
+            let (set_left, set_right) = hk.maximum_independent_set();
+            assert_eq!(
+                set_left.len() + set_right.len(),
+                left + right - matching.len()
+            );
+            for &(l, r) in &edges {
+                assert!(!(set_left.contains(&l) && set_right.contains(&r)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hungarian_with_permutations() {
+        let mut rng = Rng::new(11);
+        for _ in 0..100 {
+            let n = 1 + rng.rand(4);
+            let m = n + rng.rand(3);
+            let cost: Vec<Vec<isize>> = (0..n)
+                .map(|_| (0..m).map(|_| rng.rand(41) as isize - 20).collect())
+                .collect();
+            let naive = (0..m)
+                .permutations(n)
+                .map(|p| (0..n).map(|i| cost[i][p[i]]).sum::<isize>())
+                .min()
+                .unwrap();
+            let (total, assignment) = hungarian(&cost);
+            assert_eq!(total, naive);
+            assert_eq!(assignment.iter().unique().count(), n);
+        }
+    }
+}
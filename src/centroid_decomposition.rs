@@ -0,0 +1,242 @@
+/// Centroid Decomposition of a tree given as `Vec<Vec<usize>>`
+///
+/// The centroid tree has depth O(log n),
+/// and every path of the original tree passes through the centroid of
+/// the lowest common ancestor of its endpoints in the centroid tree.
+pub struct CentroidDecomposition {
+    /// Parent in the centroid tree
+    pub parent: Vec<Option<usize>>,
+    /// Depth in the centroid tree
+    pub depth: Vec<usize>,
+    /// Centroids in the order they are chosen, so parents come before children
+    pub order: Vec<usize>,
+}
+
+impl CentroidDecomposition {
+    /// ```
+    /// use competitive_tools_rust::centroid_decomposition::CentroidDecomposition;
+    /// // 0 - 1 - 2 - 3 - 4
+    /// let tree = vec![vec![1], vec![0, 2], vec![1, 3], vec![2, 4], vec![3]];
+    /// let cd = CentroidDecomposition::new(&tree);
+    /// assert_eq!(cd.order[0], 2);
+    /// assert_eq!(cd.parent[2], None);
+    /// assert_eq!(cd.parent[1], Some(2));
+    /// assert_eq!(cd.parent[3], Some(2));
+    /// assert_eq!(cd.depth[2], 0);
+    /// assert_eq!(cd.depth[0], 2);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the tree is not connected.
+    pub fn new(tree: &[Vec<usize>]) -> Self {
+        let n = tree.len();
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut removed = vec![false; n];
+        let mut size = vec![0; n];
+        let mut stack: Vec<(usize, Option<usize>)> = if n > 0 { vec![(0, None)] } else { vec![] };
+        while let Some((start, centroid_parent)) = stack.pop() {
+            let centroid = find_centroid(tree, start, &removed, &mut size);
+            parent[centroid] = centroid_parent;
+            depth[centroid] = centroid_parent.map_or(0, |p| depth[p] + 1);
+            order.push(centroid);
+            removed[centroid] = true;
+            for &to in &tree[centroid] {
+                if !removed[to] {
+                    stack.push((to, Some(centroid)));
+                }
+            }
+        }
+        assert_eq!(order.len(), n, "tree must be connected");
+        CentroidDecomposition {
+            parent,
+            depth,
+            order,
+        }
+    }
+
+    /// Iterate over every centroid with the component it splits.
+    /// Each item is the centroid and its branches,
+    /// where a branch is a list of (vertex, distance from the centroid)
+    /// for the vertexes of the component behind one neighbor of the centroid.
+    /// The centroid itself is not contained in any branch.
+    ///
+    /// Counting pairs whose distance is at most K:
+    /// ```
+    /// use competitive_tools_rust::centroid_decomposition::CentroidDecomposition;
+    /// // 0 - 1 - 2 - 3
+    /// //     |
+    /// //     4
+    /// let tree = vec![vec![1], vec![0, 2, 4], vec![1, 3], vec![2], vec![1]];
+    /// let k = 2;
+    /// let cd = CentroidDecomposition::new(&tree);
+    /// // number of pairs in ds with sum of distances at most k
+    /// fn count(mut ds: Vec<usize>, k: usize) -> usize {
+    ///     ds.sort();
+    ///     (0..ds.len())
+    ///         .map(|i| ds[i + 1..].iter().take_while(|&&d| ds[i] + d <= k).count())
+    ///         .sum()
+    /// }
+    /// let mut ans = 0;
+    /// for (_centroid, branches) in cd.components(&tree) {
+    ///     // pairs through the centroid = all pairs - pairs within one branch
+    ///     let mut all = vec![0];
+    ///     let mut within_branches = 0;
+    ///     for branch in branches {
+    ///         let ds: Vec<usize> = branch.iter().map(|&(_, d)| d).collect();
+    ///         within_branches += count(ds.clone(), k);
+    ///         all.extend(ds);
+    ///     }
+    ///     ans += count(all, k) - within_branches;
+    /// }
+    /// // all pairs except (0, 3), (3, 4)
+    /// assert_eq!(ans, 8);
+    /// ```
+    pub fn components<'a>(
+        &'a self,
+        tree: &'a [Vec<usize>],
+    ) -> impl Iterator<Item = (usize, Vec<Vec<(usize, usize)>>)> + 'a {
+        self.order.iter().map(move |&centroid| {
+            let is_inside = |v: usize| v != centroid && self.depth[v] > self.depth[centroid];
+            let branches = tree[centroid]
+                .iter()
+                .filter(|&&to| is_inside(to))
+                .map(|&to| {
+                    let mut branch = vec![(to, 1)];
+                    let mut stack = vec![(to, centroid, 1)];
+                    while let Some((v, from, d)) = stack.pop() {
+                        for &next in &tree[v] {
+                            if next != from && is_inside(next) {
+                                branch.push((next, d + 1));
+                                stack.push((next, v, d + 1));
+                            }
+                        }
+                    }
+                    branch
+                })
+                .collect();
+            (centroid, branches)
+        })
+    }
+}
+
+/// Centroid of the component containing start among not removed vertexes
+fn find_centroid(tree: &[Vec<usize>], start: usize, removed: &[bool], size: &mut [usize]) -> usize {
+    let mut order = vec![(start, usize::MAX)];
+    let mut i = 0;
+    while i < order.len() {
+        let (v, from) = order[i];
+        for &to in &tree[v] {
+            if to != from && !removed[to] {
+                order.push((to, v));
+            }
+        }
+        i += 1;
+    }
+    let total = order.len();
+    for &(v, _) in &order {
+        size[v] = 1;
+    }
+    for &(v, from) in order.iter().skip(1).rev() {
+        size[from] += size[v];
+    }
+    for &(v, from) in &order {
+        let largest_child = tree[v]
+            .iter()
+            .filter(|&&to| to != from && !removed[to])
+            .map(|&to| size[to])
+            .max()
+            .unwrap_or(0);
+        if largest_child * 2 <= total && (total - size[v]) * 2 <= total {
+            return v;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::centroid_decomposition::CentroidDecomposition;
+    use crate::test_util::{Rng, path_graph};
+
+    fn random_tree(n: usize, rng: &mut Rng) -> Vec<Vec<usize>> {
+        let mut tree = vec![vec![]; n];
+        for v in 1..n {
+            let p = rng.rand(v);
+            tree[v].push(p);
+            tree[p].push(v);
+        }
+        tree
+    }
+
+    fn distances_from(tree: &[Vec<usize>], s: usize) -> Vec<usize> {
+        let mut dist = vec![usize::MAX; tree.len()];
+        dist[s] = 0;
+        let mut stack = vec![s];
+        while let Some(v) = stack.pop() {
+            for &to in &tree[v] {
+                if dist[to] == usize::MAX {
+                    dist[to] = dist[v] + 1;
+                    stack.push(to);
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn test_depth_is_logarithmic() {
+        let n = 1 << 12;
+        let path: Vec<Vec<usize>> = path_graph(n);
+        let cd = CentroidDecomposition::new(&path);
+        assert!(*cd.depth.iter().max().unwrap() <= 12);
+        assert_eq!(cd.order.len(), n);
+    }
+
+    #[test]
+    fn test_components_cover_all_pairs_once() {
+        let mut rng = Rng::new(7);
+        for n in 1..40 {
+            let tree = random_tree(n, &mut rng);
+            let cd = CentroidDecomposition::new(&tree);
+            let mut pairs = vec![vec![0; n]; n];
+            for (centroid, branches) in cd.components(&tree) {
+                let dist = distances_from(&tree, centroid);
+                let mut all = vec![(centroid, 0)];
+                for branch in &branches {
+                    for &(v, d) in branch {
+                        assert_eq!(dist[v], d);
+                    }
+                }
+                for branch in branches {
+                    for &(v, _) in &branch {
+                        for &(u, _) in &all {
+                            pairs[u][v] += 1;
+                            pairs[v][u] += 1;
+                        }
+                    }
+                    all.extend(branch);
+                }
+            }
+            for (u, row) in pairs.iter().enumerate() {
+                for (v, &count) in row.iter().enumerate() {
+                    assert_eq!(count, if u == v { 0 } else { 1 });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let cd = CentroidDecomposition::new(&[]);
+        assert!(cd.order.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "tree must be connected")]
+    fn test_forest() {
+        let forest = vec![vec![1], vec![0], vec![3], vec![2]];
+        CentroidDecomposition::new(&forest);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::graph::AdjacencyList;
+
+/// Dominator tree of a directed graph given as `Vec<Vec<usize>>` from source:
+/// u dominates v if every path from source to v passes through u.
+/// Built by Lengauer-Tarjan in O(E log V) with iterative DFS and path compression.
+pub struct DominatorTree {
+    /// Immediate dominator, None for the source and unreachable vertexes
+    pub idom: Vec<Option<usize>>,
+    pub source: usize,
+    /// Whether each vertex is reachable from source
+    pub reachable: Vec<bool>,
+}
+
+impl DominatorTree {
+    /// ```
+    /// use competitive_tools_rust::dominator_tree::DominatorTree;
+    /// // 0 -> 1 -> 2 -> 4
+    /// //  \-> 3 --^
+    /// // 5 is unreachable
+    /// let graph = vec![vec![1, 3], vec![2], vec![4], vec![2], vec![], vec![0]];
+    /// let tree = DominatorTree::new(&graph, 0);
+    /// assert_eq!(tree.idom, vec![None, Some(0), Some(0), Some(0), Some(2), None]);
+    /// assert_eq!(tree.reachable, vec![true, true, true, true, true, false]);
+    /// ```
+    pub fn new(graph: &[Vec<usize>], source: usize) -> Self {
+        let n = graph.len();
+        let reversed = graph.to_vec().rev_edge_direction();
+
+        // DFS pre-order numbering
+        let mut num: Vec<Option<usize>> = vec![None; n];
+        let mut vertex = vec![];
+        let mut dfs_parent = vec![0; n];
+        num[source] = Some(0);
+        vertex.push(source);
+        let mut stack = vec![(source, 0)];
+        while let Some((v, i)) = stack.pop() {
+            if let Some(&to) = graph[v].get(i) {
+                stack.push((v, i + 1));
+                if num[to].is_none() {
+                    num[to] = Some(vertex.len());
+                    vertex.push(to);
+                    dfs_parent[vertex.len() - 1] = num[v].unwrap();
+                    stack.push((to, 0));
+                }
+            }
+        }
+
+        // everything below is indexed by the DFS number
+        let k = vertex.len();
+        let mut semi: Vec<usize> = (0..k).collect();
+        let mut idom: Vec<usize> = vec![0; k];
+        let mut ancestor: Vec<Option<usize>> = vec![None; k];
+        let mut label: Vec<usize> = (0..k).collect();
+        let mut bucket: Vec<Vec<usize>> = vec![vec![]; k];
+        for w in (1..k).rev() {
+            for &pred in &reversed[vertex[w]] {
+                if let Some(v) = num[pred] {
+                    let u = eval(v, &mut ancestor, &mut label, &semi);
+                    semi[w] = semi[w].min(semi[u]);
+                }
+            }
+            bucket[semi[w]].push(w);
+            let p = dfs_parent[w];
+            ancestor[w] = Some(p);
+            for v in std::mem::take(&mut bucket[p]) {
+                let u = eval(v, &mut ancestor, &mut label, &semi);
+                idom[v] = if semi[u] < semi[v] { u } else { p };
+            }
+        }
+        for w in 1..k {
+            if idom[w] != semi[w] {
+                idom[w] = idom[idom[w]];
+            }
+        }
+
+        let mut result = vec![None; n];
+        for w in 1..k {
+            result[vertex[w]] = Some(vertex[idom[w]]);
+        }
+        DominatorTree {
+            idom: result,
+            source,
+            reachable: num.iter().map(|x| x.is_some()).collect(),
+        }
+    }
+
+    /// Dominators of v from v itself up to the source, empty if v is unreachable
+    /// ```
+    /// use competitive_tools_rust::dominator_tree::DominatorTree;
+    /// // 0 -> 1 -> 2 -> 4
+    /// //  \-> 3 --^
+    /// let graph = vec![vec![1, 3], vec![2], vec![4], vec![2], vec![]];
+    /// let tree = DominatorTree::new(&graph, 0);
+    /// assert_eq!(tree.dominators(4), vec![4, 2, 0]);
+    /// ```
+    pub fn dominators(&self, v: usize) -> Vec<usize> {
+        if !self.reachable[v] {
+            return vec![];
+        }
+        let mut dominators = vec![v];
+        let mut u = v;
+        while let Some(d) = self.idom[u] {
+            dominators.push(d);
+            u = d;
+        }
+        dominators
+    }
+
+    /// Children lists of the dominator tree
+    pub fn children(&self) -> Vec<Vec<usize>> {
+        let mut children = vec![vec![]; self.idom.len()];
+        for (v, d) in self.idom.iter().enumerate() {
+            if let Some(d) = d {
+                children[*d].push(v);
+            }
+        }
+        children
+    }
+}
+
+/// Vertex with the minimum semi on the path from v to the root of its linked tree,
+/// compressing the path iteratively
+fn eval(v: usize, ancestor: &mut [Option<usize>], label: &mut [usize], semi: &[usize]) -> usize {
+    let Some(a) = ancestor[v] else { return v };
+    if ancestor[a].is_some() {
+        let mut path = vec![];
+        let mut x = v;
+        while let Some(a) = ancestor[x]
+            && ancestor[a].is_some()
+        {
+            path.push(x);
+            x = a;
+        }
+        // from the one nearest to the root
+        for &x in path.iter().rev() {
+            let a = ancestor[x].unwrap();
+            if semi[label[a]] < semi[label[x]] {
+                label[x] = label[a];
+            }
+            ancestor[x] = ancestor[a];
+        }
+    }
+    label[v]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dominator_tree::DominatorTree;
+    use crate::test_util::{Rng, directed_path_graph};
+
+    fn reachable(graph: &[Vec<usize>], source: usize, removed: Option<usize>) -> Vec<bool> {
+        let mut seen = vec![false; graph.len()];
+        if Some(source) == removed {
+            return seen;
+        }
+        seen[source] = true;
+        let mut stack = vec![source];
+        while let Some(v) = stack.pop() {
+            for &to in &graph[v] {
+                if !seen[to] && Some(to) != removed {
+                    seen[to] = true;
+                    stack.push(to);
+                }
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn test_with_naive_removal() {
+        let mut rng = Rng::new(59);
+        for _ in 0..300 {
+            let n = 1 + rng.rand(10);
+            let mut graph = vec![vec![]; n];
+            for _ in 0..rng.rand(3 * n) {
+                graph[rng.rand(n)].push(rng.rand(n));
+            }
+            let source = rng.rand(n);
+            let tree = DominatorTree::new(&graph, source);
+            let base = reachable(&graph, source, None);
+            assert_eq!(tree.reachable, base);
+            for (v, &is_reachable) in base.iter().enumerate() {
+                if !is_reachable {
+                    assert_eq!(tree.dominators(v), vec![]);
+                    continue;
+                }
+                let mut naive: Vec<usize> = (0..n)
+                    .filter(|&u| u == v || !reachable(&graph, source, Some(u))[v])
+                    .collect();
+                let mut dominators = tree.dominators(v);
+                // dominators are totally ordered, so the chain must be exactly them
+                naive.sort();
+                dominators.sort();
+                assert_eq!(dominators, naive);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deep_path_graph() {
+        let n = 200_000;
+        let mut graph = directed_path_graph(n);
+        graph[n - 1].push(0);
+        let tree = DominatorTree::new(&graph, 0);
+        assert_eq!(tree.idom[n - 1], Some(n - 2));
+        assert_eq!(tree.dominators(n - 1).len(), n);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::segment_tree::SegmentTree;
+use std::ops::Range;
+
+/// Euler Tour of a rooted tree given as `Vec<Vec<usize>>`
+///
+/// The subtree of v is `tin[v]..tout[v]` in pre-order,
+/// so subtree queries become range queries.
+pub struct EulerTour {
+    /// Pre-order index of each vertex
+    pub tin: Vec<usize>,
+    /// tin of v plus the size of the subtree of v
+    pub tout: Vec<usize>,
+    /// order\[i\] is the vertex whose tin is i
+    pub order: Vec<usize>,
+    pub depth: Vec<usize>,
+    /// Every visit of vertexes, including returns from children (length 2n - 1)
+    pub tour: Vec<usize>,
+    /// Index of the first visit of each vertex in tour
+    pub first: Vec<usize>,
+}
+
+impl EulerTour {
+    /// ```
+    /// use competitive_tools_rust::euler_tour::EulerTour;
+    /// // 0 - 1 - 2
+    /// // |
+    /// // 3
+    /// let tree = vec![vec![1, 3], vec![0, 2], vec![1], vec![0]];
+    /// let et = EulerTour::new(&tree, 0);
+    /// assert_eq!(et.order, vec![0, 1, 2, 3]);
+    /// assert_eq!(et.tin, vec![0, 1, 2, 3]);
+    /// assert_eq!(et.tout, vec![4, 3, 3, 4]);
+    /// assert_eq!(et.tour, vec![0, 1, 2, 1, 0, 3, 0]);
+    /// assert_eq!(et.first, vec![0, 1, 2, 5]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the tree is not connected.
+    pub fn new(tree: &[Vec<usize>], root: usize) -> Self {
+        let n = tree.len();
+        let mut tin = vec![0; n];
+        let mut tout = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut depth = vec![0; n];
+        let mut tour = Vec::with_capacity(2 * n);
+        let mut first = vec![0; n];
+        let mut parent = vec![usize::MAX; n];
+        // (vertex, index of the next child to visit)
+        let mut stack = vec![(root, 0)];
+        tin[root] = 0;
+        order.push(root);
+        first[root] = 0;
+        tour.push(root);
+        while let Some((v, i)) = stack.pop() {
+            if let Some(&to) = tree[v].get(i) {
+                stack.push((v, i + 1));
+                if to == parent[v] {
+                    continue;
+                }
+                parent[to] = v;
+                depth[to] = depth[v] + 1;
+                tin[to] = order.len();
+                order.push(to);
+                first[to] = tour.len();
+                tour.push(to);
+                stack.push((to, 0));
+            } else {
+                tout[v] = order.len();
+                if let Some(&(p, _)) = stack.last() {
+                    tour.push(p);
+                }
+            }
+        }
+        assert_eq!(order.len(), n, "tree must be connected");
+        EulerTour {
+            tin,
+            tout,
+            order,
+            depth,
+            tour,
+            first,
+        }
+    }
+
+    /// Range of pre-order indexes of the subtree of v
+    /// ```
+    /// use competitive_tools_rust::euler_tour::EulerTour;
+    /// use competitive_tools_rust::segment_tree::SegmentTree;
+    /// // 0 - 1 - 2
+    /// // |
+    /// // 3
+    /// let tree = vec![vec![1, 3], vec![0, 2], vec![1], vec![0]];
+    /// let weights = vec![8, 5, 7, 2];
+    /// let et = EulerTour::new(&tree, 0);
+    /// let seg = SegmentTree::from_vec(et.order.iter().map(|&v| weights[v]).collect(), usize::MAX);
+    /// assert_eq!(seg.query(et.subtree_range(0)), 2);
+    /// assert_eq!(seg.query(et.subtree_range(1)), 5);
+    /// assert_eq!(seg.query(et.subtree_range(2)), 7);
+    /// ```
+    pub fn subtree_range(&self, v: usize) -> Range<usize> {
+        self.tin[v]..self.tout[v]
+    }
+
+    /// Segment tree of (depth, vertex) over tour for `lca`
+    pub fn lca_tree(&self) -> SegmentTree<(usize, usize)> {
+        SegmentTree::from_vec(
+            self.tour.iter().map(|&v| (self.depth[v], v)).collect(),
+            (usize::MAX, usize::MAX),
+        )
+    }
+
+    /// Lowest Common Ancestor by range minimum of depth on tour
+    /// ```
+    /// use competitive_tools_rust::euler_tour::EulerTour;
+    /// // 0 - 1 - 2
+    /// // |   |
+    /// // 3   4
+    /// let tree = vec![vec![1, 3], vec![0, 2, 4], vec![1], vec![0], vec![1]];
+    /// let et = EulerTour::new(&tree, 0);
+    /// let rmq = et.lca_tree();
+    /// assert_eq!(et.lca(&rmq, 2, 4), 1);
+    /// assert_eq!(et.lca(&rmq, 2, 3), 0);
+    /// assert_eq!(et.lca(&rmq, 1, 2), 1);
+    /// assert_eq!(et.lca(&rmq, 3, 3), 3);
+    /// ```
+    pub fn lca(&self, rmq: &SegmentTree<(usize, usize)>, u: usize, v: usize) -> usize {
+        let (a, b) = if self.first[u] <= self.first[v] {
+            (self.first[u], self.first[v])
+        } else {
+            (self.first[v], self.first[u])
+        };
+        rmq.query(a..=b).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::euler_tour::EulerTour;
+    use crate::test_util::path_graph;
+
+    #[test]
+    fn test_deep_path_graph() {
+        let n = 200_000;
+        let tree: Vec<Vec<usize>> = path_graph(n);
+        let et = EulerTour::new(&tree, 0);
+        assert_eq!(et.tin[n - 1], n - 1);
+        assert_eq!(et.tout[0], n);
+        assert_eq!(et.tout[n - 1], n);
+        assert_eq!(et.tour.len(), 2 * n - 1);
+        let rmq = et.lca_tree();
+        assert_eq!(et.lca(&rmq, n - 1, n / 2), n / 2);
+    }
+
+    #[test]
+    fn test_lca_with_naive() {
+        // 0 - 1 - 2 - 3
+        //     |   |
+        //     4   5 - 6
+        //     |
+        //     7
+        let edges = vec![(0, 1), (1, 2), (2, 3), (1, 4), (2, 5), (5, 6), (4, 7)];
+        let mut tree = vec![vec![]; 8];
+        for (u, v) in edges {
+            tree[u].push(v);
+            tree[v].push(u);
+        }
+        let root = 5;
+        let et = EulerTour::new(&tree, root);
+        let rmq = et.lca_tree();
+        let is_ancestor = |a: usize, v: usize| et.subtree_range(a).contains(&et.tin[v]);
+        for u in 0..8 {
+            for v in 0..8 {
+                let naive = (0..8)
+                    .filter(|&a| is_ancestor(a, u) && is_ancestor(a, v))
+                    .max_by_key(|&a| et.depth[a])
+                    .unwrap();
+                assert_eq!(et.lca(&rmq, u, v), naive);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "tree must be connected")]
+    fn test_forest() {
+        let forest = vec![vec![1], vec![0], vec![3], vec![2]];
+        EulerTour::new(&forest, 0);
+    }
+}
@@ -0,0 +1,143 @@
+/// Functional graph: every vertex v has exactly one outgoing edge to next\[v\].
+/// Each connected component is a cycle with trees hanging into it.
+pub struct FunctionalGraph {
+    pub next: Vec<usize>,
+    /// Number of steps until entering a cycle (0 for vertexes on cycles)
+    pub tail_length: Vec<usize>,
+    /// Id of the cycle which each vertex finally reaches
+    pub cycle_id: Vec<usize>,
+    /// Position of each vertex in its cycle, None for vertexes not on cycles
+    pub cycle_index: Vec<Option<usize>>,
+    /// Vertexes of each cycle in the order of edges
+    pub cycles: Vec<Vec<usize>>,
+    /// doubling\[i\]\[v\] is the vertex after 2^i steps from v (2^i < n)
+    doubling: Vec<Vec<usize>>,
+}
+
+impl FunctionalGraph {
+    /// ```
+    /// use competitive_tools_rust::functional_graph::FunctionalGraph;
+    /// // 0 -> 1 -> 2 -> 3 -> 1, 4 -> 4
+    /// let graph = FunctionalGraph::new(vec![1, 2, 3, 1, 4]);
+    /// assert_eq!(graph.tail_length, vec![1, 0, 0, 0, 0]);
+    /// assert_eq!(graph.cycle_id, vec![0, 0, 0, 0, 1]);
+    /// assert_eq!(graph.cycle_index, vec![None, Some(0), Some(1), Some(2), Some(0)]);
+    /// assert_eq!(graph.cycles, vec![vec![1, 2, 3], vec![4]]);
+    /// ```
+    pub fn new(next: Vec<usize>) -> Self {
+        let n = next.len();
+        // 0: not visited, 1: on the current walk, 2: done
+        let mut state = vec![0u8; n];
+        let mut tail_length = vec![0; n];
+        let mut cycle_id = vec![0; n];
+        let mut cycle_index = vec![None; n];
+        let mut cycles: Vec<Vec<usize>> = vec![];
+        for start in 0..n {
+            if state[start] != 0 {
+                continue;
+            }
+            let mut path = vec![];
+            let mut v = start;
+            while state[v] == 0 {
+                state[v] = 1;
+                path.push(v);
+                v = next[v];
+            }
+            if state[v] == 1 {
+                let pos = path.iter().position(|&x| x == v).unwrap();
+                let cycle = path.split_off(pos);
+                for (i, &c) in cycle.iter().enumerate() {
+                    cycle_id[c] = cycles.len();
+                    cycle_index[c] = Some(i);
+                    state[c] = 2;
+                }
+                cycles.push(cycle);
+            }
+            for &x in path.iter().rev() {
+                tail_length[x] = tail_length[next[x]] + 1;
+                cycle_id[x] = cycle_id[next[x]];
+                state[x] = 2;
+            }
+        }
+
+        let mut doubling = vec![next.clone()];
+        while 1 << doubling.len() < n {
+            let last = doubling.last().unwrap();
+            let doubled = (0..n).map(|v| last[last[v]]).collect();
+            doubling.push(doubled);
+        }
+
+        FunctionalGraph {
+            next,
+            tail_length,
+            cycle_id,
+            cycle_index,
+            cycles,
+            doubling,
+        }
+    }
+
+    /// Vertex after k steps from v in O(log n), for any k including 10^18
+    /// ```
+    /// use competitive_tools_rust::functional_graph::FunctionalGraph;
+    /// // 0 -> 1 -> 2 -> 3 -> 1, 4 -> 4
+    /// let graph = FunctionalGraph::new(vec![1, 2, 3, 1, 4]);
+    /// assert_eq!(graph.kth(0, 0), 0);
+    /// assert_eq!(graph.kth(0, 1), 1);
+    /// assert_eq!(graph.kth(0, 4), 1);
+    /// assert_eq!(graph.kth(0, 5), 2);
+    /// assert_eq!(graph.kth(2, 1_000_000_000_000_000_000), 3);
+    /// assert_eq!(graph.kth(4, 1_000_000_000_000_000_000), 4);
+    /// ```
+    pub fn kth(&self, v: usize, k: usize) -> usize {
+        if k < self.tail_length[v] {
+            return self.jump(v, k);
+        }
+        let entrance = self.jump(v, self.tail_length[v]);
+        let cycle = &self.cycles[self.cycle_id[entrance]];
+        let index = self.cycle_index[entrance].unwrap();
+        cycle[(index + (k - self.tail_length[v]) % cycle.len()) % cycle.len()]
+    }
+
+    /// Vertex after k steps from v by the doubling table, where k < 2^(table size)
+    fn jump(&self, mut v: usize, k: usize) -> usize {
+        for (i, table) in self.doubling.iter().enumerate() {
+            if (k >> i) & 1 == 1 {
+                v = table[v];
+            }
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::functional_graph::FunctionalGraph;
+    use crate::test_util::Rng;
+
+    #[test]
+    fn test_kth_with_naive() {
+        let mut rng = Rng::new(37);
+        for _ in 0..50 {
+            let n = 1 + rng.rand(20);
+            let next: Vec<usize> = (0..n).map(|_| rng.rand(n)).collect();
+            let graph = FunctionalGraph::new(next.clone());
+            for v in 0..n {
+                let mut u = v;
+                for k in 0..3 * n {
+                    assert_eq!(graph.kth(v, k), u);
+                    if k == graph.tail_length[v] {
+                        assert!(graph.cycle_index[u].is_some());
+                        assert_eq!(graph.cycle_id[u], graph.cycle_id[v]);
+                    }
+                    u = next[u];
+                }
+            }
+            let cycle_total: usize = graph.cycles.iter().map(|c| c.len()).sum();
+            assert_eq!(
+                cycle_total,
+                graph.cycle_index.iter().filter(|i| i.is_some()).count()
+            );
+        }
+    }
+}
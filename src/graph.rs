@@ -16,24 +16,104 @@ impl Dag for Vec<Vec<usize>> {
     /// assert_eq!(dag.topological_sort(), vec![1, 2, 0]);
     /// ```
     fn topological_sort(&self) -> Vec<usize> {
-        /// post-order Depth First Search
-        fn dfs(me: &[Vec<usize>], seen: &mut Vec<bool>, i: usize, rev_order: &mut Vec<usize>) {
-            seen[i] = true;
-            me[i].iter().for_each(|&to| {
-                if !seen[to] {
-                    dfs(me, seen, to, rev_order);
-                }
-            });
-            rev_order.push(i);
-        }
-        let mut seen: Vec<bool> = vec![false; self.len()];
-        let mut rev_order: Vec<usize> = vec![];
-        for i in 0..self.len() {
-            if !seen[i] {
-                dfs(self, &mut seen, i, &mut rev_order);
+        topological_sort(self.len(), adjacent_in(self))
+    }
+}
+
+/// Neighbors of each vertex of `Vec<Vec<usize>>`,
+/// for the algorithms below which are shared with `Graph`
+fn adjacent_in<'a>(graph: &'a [Vec<usize>]) -> impl Fn(usize) -> Copied<Iter<'a, usize>> {
+    move |v| graph[v].iter().copied()
+}
+
+fn topological_sort<I>(n: usize, adjacent: impl Fn(usize) -> I) -> Vec<usize>
+where
+    I: Iterator<Item = usize>,
+{
+    let mut seen: Vec<bool> = vec![false; n];
+    let mut rev_order: Vec<usize> = vec![];
+    for i in 0..n {
+        if !seen[i] {
+            dfs(&adjacent, i, &mut seen, |_, _| {}, |v, _| rev_order.push(v));
+        }
+    }
+    rev_order.into_iter().rev().collect()
+}
+
+pub trait DepthFirstSearch {
+    fn dfs<Pre, Post>(&self, start: usize, seen: &mut [bool], pre: Pre, post: Post)
+    where
+        Pre: FnMut(usize, Option<usize>),
+        Post: FnMut(usize, Option<usize>);
+}
+
+impl DepthFirstSearch for Vec<Vec<usize>> {
+    /// Iterative Depth First Search from start, which never overflows the stack.
+    /// pre(v, parent) is called when v is visited,
+    /// and post(v, parent) is called after all vertexes reachable from v are visited.
+    /// Vertexes marked in seen are skipped, and visited vertexes are marked.
+    /// The visiting order is the same as the recursive one.
+    /// ```
+    /// use competitive_tools_rust::graph::DepthFirstSearch;
+    /// // 0 -> 1 -> 2
+    /// // |
+    /// // v
+    /// // 3 -> 1
+    /// let graph: Vec<Vec<usize>> = vec![vec![1, 3], vec![2], vec![], vec![1]];
+    /// let mut seen = vec![false; graph.len()];
+    /// let mut pre_order = vec![];
+    /// let mut post_order = vec![];
+    /// graph.dfs(
+    ///     0,
+    ///     &mut seen,
+    ///     |v, parent| pre_order.push((v, parent)),
+    ///     |v, _| post_order.push(v),
+    /// );
+    /// assert_eq!(pre_order, vec![(0, None), (1, Some(0)), (2, Some(1)), (3, Some(0))]);
+    /// assert_eq!(post_order, vec![2, 1, 3, 0]);
+    /// assert_eq!(seen, vec![true; 4]);
+    /// ```
+    fn dfs<Pre, Post>(&self, start: usize, seen: &mut [bool], pre: Pre, post: Post)
+    where
+        Pre: FnMut(usize, Option<usize>),
+        Post: FnMut(usize, Option<usize>),
+    {
+        dfs(adjacent_in(self), start, seen, pre, post);
+    }
+}
+
+fn dfs<I, Pre, Post>(
+    adjacent: impl Fn(usize) -> I,
+    start: usize,
+    seen: &mut [bool],
+    mut pre: Pre,
+    mut post: Post,
+) where
+    I: Iterator<Item = usize>,
+    Pre: FnMut(usize, Option<usize>),
+    Post: FnMut(usize, Option<usize>),
+{
+    if seen[start] {
+        return;
+    }
+    seen[start] = true;
+    pre(start, None);
+    // (vertex, parent, edges not followed yet)
+    let mut stack = vec![(start, None, adjacent(start))];
+    while let Some((v, parent, edges)) = stack.last_mut() {
+        let (v, parent) = (*v, *parent);
+        match edges.next() {
+            Some(to) if !seen[to] => {
+                seen[to] = true;
+                pre(to, Some(v));
+                stack.push((to, Some(v), adjacent(to)));
+            }
+            Some(_) => {}
+            None => {
+                stack.pop();
+                post(v, parent);
             }
         }
-        rev_order.into_iter().rev().collect()
     }
 }
 
@@ -110,39 +190,39 @@ impl AdjacencyList for Vec<Vec<usize>> {
     /// assert_eq!(scc_order, vec![0, 0, 1, 0]);
     /// ```
     fn strongly_connected_component(&self) -> Vec<usize> {
-        fn dfs(
-            start_id: usize,
-            graph: &[Vec<usize>],
-            seen: &mut Vec<bool>,
-            order: &mut [usize],
-            scc_id: usize,
-        ) {
-            seen[start_id] = true;
-            order[start_id] = scc_id;
-            for &to in &graph[start_id] {
-                if !seen[to] {
-                    dfs(to, graph, seen, order, scc_id);
-                }
-            }
-        }
-        let topological_order = self.topological_sort();
-        let reversed = self.rev_edge_direction();
-        let mut order = vec![0; self.len()];
-        let mut seen: Vec<bool> = vec![false; self.len()];
-        let mut scc_id: usize = 0;
-        for start_id in topological_order {
-            if !seen[start_id] {
-                dfs(start_id, &reversed, &mut seen, &mut order, scc_id);
-                scc_id += 1;
-            }
+        strongly_connected_component(self.len(), adjacent_in(self))
+    }
+}
+
+fn strongly_connected_component<I>(n: usize, adjacent: impl Fn(usize) -> I) -> Vec<usize>
+where
+    I: Iterator<Item = usize>,
+{
+    let topological_order = topological_sort(n, &adjacent);
+    let mut reversed: Vec<Vec<usize>> = vec![vec![]; n];
+    for v in 0..n {
+        adjacent(v).for_each(|to| reversed[to].push(v));
+    }
+    let mut order = vec![0; n];
+    let mut seen: Vec<bool> = vec![false; n];
+    let mut scc_id: usize = 0;
+    for start_id in topological_order {
+        if !seen[start_id] {
+            reversed.dfs(start_id, &mut seen, |v, _| order[v] = scc_id, |_, _| {});
+            scc_id += 1;
         }
-        order
     }
+    order
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::graph::{AdjacencyList, Dag};
+    use crate::graph::{
+        AdjacencyList, BipartiteGraph, Dag, Edge, EulerianTrail, Graph, Rerooting, SmallGraph,
+        dijkstra, dijkstra_with_count, directed_eulerian_trail, k_shortest_walks,
+        undirected_eulerian_trail,
+    };
+    use crate::test_util::{Rng, directed_path_graph, path_graph};
 
     #[test]
     fn test_topological_sort_for_cyclic() {
@@ -186,10 +266,364 @@ mod tests {
             vec![1, 1, 1, 0],
         );
     }
+
+    #[test]
+    fn test_topological_sort_for_deep_path() {
+        let n = 1_000_000;
+        let adjacency_list = directed_path_graph(n);
+        assert_eq!(
+            adjacency_list.topological_sort(),
+            (0..n).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_strongly_connected_component_for_deep_cycle() {
+        let n = 1_000_000;
+        let mut adjacency_list = directed_path_graph(n);
+        adjacency_list[n - 1].push(0);
+        assert_eq!(adjacency_list.strongly_connected_component(), vec![0; n]);
+        adjacency_list[n - 1].clear();
+        assert_eq!(
+            adjacency_list.strongly_connected_component(),
+            (0..n).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_checked_bi_partition_or_odd_cycle() {
+        let mut rng = Rng::new(17);
+        for _ in 0..300 {
+            let n = 1 + rng.rand(8);
+            let mut adjacency_list: Vec<Vec<usize>> = vec![vec![]; n];
+            for _ in 0..rng.rand(8) {
+                let (u, v) = (rng.rand(n), rng.rand(n));
+                adjacency_list[u].push(v);
+                if u != v {
+                    adjacency_list[v].push(u);
+                }
+            }
+            match adjacency_list.checked_bi_partition() {
+                Some(bipartition) => {
+                    assert_eq!(adjacency_list.odd_cycle(), None);
+                    for (u, edges) in adjacency_list.iter().enumerate() {
+                        for &v in edges {
+                            assert_ne!(bipartition.side[u], bipartition.side[v]);
+                            assert_eq!(bipartition.component[u], bipartition.component[v]);
+                        }
+                    }
+                    let total: usize = bipartition.sizes.iter().map(|(x, y)| x + y).sum();
+                    assert_eq!(total, n);
+                }
+                None => {
+                    let cycle = adjacency_list.odd_cycle().unwrap();
+                    assert_eq!(cycle.len() % 2, 1);
+                    for i in 0..cycle.len() {
+                        let next = cycle[(i + 1) % cycle.len()];
+                        assert!(adjacency_list[cycle[i]].contains(&next));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_eulerian_trail_for_de_bruijn_sequence() {
+        // vertexes are 3 bits and edges append 1 bit
+        let k = 4;
+        let n = 1 << (k - 1);
+        let edges: Vec<(usize, usize)> = (0..1 << k).map(|e| (e >> 1, e & (n - 1))).collect();
+        let (vertexes, edge_ids) = directed_eulerian_trail(n, &edges).unwrap();
+        assert_eq!(vertexes.first(), vertexes.last());
+        let sequence: Vec<usize> = edge_ids.iter().map(|&e| e & 1).collect();
+        let mut seen = vec![false; 1 << k];
+        for i in 0..sequence.len() {
+            let word = (0..k).fold(0, |acc, j| acc * 2 + sequence[(i + j) % sequence.len()]);
+            seen[word] = true;
+        }
+        assert!(seen.into_iter().all(|b| b));
+    }
+
+    /// Whether some order of edges forms a trail, by brute force
+    fn naive_has_trail(n: usize, edges: &[(usize, usize)], directed: bool) -> bool {
+        fn rec(v: usize, used: &mut Vec<bool>, edges: &[(usize, usize)], directed: bool) -> bool {
+            if used.iter().all(|&u| u) {
+                return true;
+            }
+            for i in 0..edges.len() {
+                if used[i] {
+                    continue;
+                }
+                let (a, b) = edges[i];
+                let to = if a == v {
+                    b
+                } else if !directed && b == v {
+                    a
+                } else {
+                    continue;
+                };
+                used[i] = true;
+                if rec(to, used, edges, directed) {
+                    return true;
+                }
+                used[i] = false;
+            }
+            false
+        }
+        (0..n).any(|s| rec(s, &mut vec![false; edges.len()], edges, directed))
+    }
+
+    #[test]
+    fn test_eulerian_trail_with_naive() {
+        let mut rng = Rng::new(29);
+        for _ in 0..300 {
+            let n = 1 + rng.rand(4);
+            let edges: Vec<(usize, usize)> = (0..1 + rng.rand(6))
+                .map(|_| (rng.rand(n), rng.rand(n)))
+                .collect();
+            for directed in [true, false] {
+                let result = if directed {
+                    directed_eulerian_trail(n, &edges)
+                } else {
+                    undirected_eulerian_trail(n, &edges)
+                };
+                assert_eq!(result.is_some(), naive_has_trail(n, &edges, directed));
+                if let Some((vertexes, edge_ids)) = result {
+                    assert_eq!(vertexes.len(), edges.len() + 1);
+                    let mut sorted_ids = edge_ids.clone();
+                    sorted_ids.sort();
+                    assert_eq!(sorted_ids, (0..edges.len()).collect::<Vec<_>>());
+                    for (i, &id) in edge_ids.iter().enumerate() {
+                        let (a, b) = edges[id];
+                        let (u, v) = (vertexes[i], vertexes[i + 1]);
+                        assert!((a, b) == (u, v) || !directed && (b, a) == (u, v));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rerooting_sum_of_distances_for_path() {
+        let n = 200_000;
+        let tree: Vec<Vec<usize>> = path_graph(n);
+        let dp = tree.rerooting(
+            (0, 0),
+            |a: &(usize, usize), b: &(usize, usize)| (a.0 + b.0, a.1 + b.1),
+            |acc, _| (acc.0 + 1, acc.1 + acc.0 + 1),
+        );
+        for i in [0, 1, n / 2, n - 1] {
+            let expected = i * (i + 1) / 2 + (n - 1 - i) * (n - i) / 2;
+            assert_eq!(dp[i].1 - dp[i].0, expected);
+        }
+    }
+
+    #[test]
+    fn test_rerooting_for_forest() {
+        // 0 - 1   2   3 - 4 - 5
+        let forest: Vec<Vec<usize>> = vec![vec![1], vec![0], vec![], vec![4], vec![3, 5], vec![4]];
+        let sizes = forest.rerooting(0, |a: &usize, b: &usize| a + b, |acc, _| acc + 1);
+        assert_eq!(sizes, vec![2, 2, 1, 3, 3, 3]);
+    }
+
+    #[test]
+    fn test_small_graph_with_naive() {
+        let mut rng = Rng::new(47);
+        for _ in 0..200 {
+            let n = 1 + rng.rand(9);
+            let mut graph = vec![vec![]; n];
+            let mut adjacent = vec![vec![false; n]; n];
+            for _ in 0..rng.rand(n * n / 2 + 1) {
+                let (u, v) = (rng.rand(n), rng.rand(n));
+                graph[u].push(v);
+                graph[v].push(u);
+                adjacent[u][v] = true;
+                adjacent[v][u] = true;
+            }
+            let is_independent = |set: &[usize]| {
+                set.iter()
+                    .all(|&u| set.iter().all(|&v| u == v || !adjacent[u][v]))
+            };
+            let is_clique = |set: &[usize]| {
+                set.iter()
+                    .all(|&u| set.iter().all(|&v| u == v || adjacent[u][v]))
+            };
+            let subsets: Vec<Vec<usize>> = (0..1usize << n)
+                .map(|mask| (0..n).filter(|&v| mask >> v & 1 == 1).collect())
+                .collect();
+            let max_independent = subsets
+                .iter()
+                .filter(|s| is_independent(s))
+                .map(|s| s.len())
+                .max();
+            let max_clique = subsets
+                .iter()
+                .filter(|s| is_clique(s))
+                .map(|s| s.len())
+                .max();
+
+            let independent_set = graph.maximum_independent_set();
+            assert!(is_independent(&independent_set));
+            assert_eq!(Some(independent_set.len()), max_independent);
+            let clique = graph.maximum_clique();
+            assert!(is_clique(&clique));
+            assert_eq!(Some(clique.len()), max_clique);
+
+            let colorable = |k: usize| {
+                (0..k.pow(n as u32)).any(|code| {
+                    let color: Vec<usize> = (0..n).map(|v| code / k.pow(v as u32) % k).collect();
+                    (0..n)
+                        .all(|u| (0..n).all(|v| u == v || !adjacent[u][v] || color[u] != color[v]))
+                })
+            };
+            let naive = (1..=n).find(|&k| colorable(k)).unwrap();
+            assert_eq!(graph.chromatic_number(), naive);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chromatic_number supports at most 25 vertexes")]
+    fn test_chromatic_number_of_large_graph() {
+        let graph: Vec<Vec<usize>> = vec![vec![]; 26];
+        graph.chromatic_number();
+    }
+
+    #[test]
+    fn test_dijkstra_variants_with_walk_counting() {
+        let mut rng = Rng::new(61);
+        for _ in 0..100 {
+            let n = 1 + rng.rand(6);
+            let mut edges_list: Vec<Vec<Edge>> = vec![vec![]; n];
+            for _ in 0..rng.rand(3 * n) {
+                edges_list[rng.rand(n)].push(Edge {
+                    to: rng.rand(n),
+                    cost: 1 + rng.rand(3),
+                });
+            }
+            // walks[len][v]: number of walks from 0 to v of length len,
+            // as (modulo 7, capped at 6 not to overflow)
+            let max_len = 30;
+            let mut walks = vec![vec![(0, 0); n]; max_len + 1];
+            walks[0][0] = (1, 1);
+            for len in 0..max_len {
+                for v in 0..n {
+                    let (count_mod, count_capped) = walks[len][v];
+                    for e in &edges_list[v] {
+                        if len + e.cost <= max_len {
+                            let next: &mut (usize, usize) = &mut walks[len + e.cost][e.to];
+                            next.0 = (next.0 + count_mod) % 7;
+                            next.1 = (next.1 + count_capped).min(6);
+                        }
+                    }
+                }
+            }
+            let (dists, counts) = dijkstra_with_count(0, n, &edges_list, 7);
+            assert_eq!(dists, dijkstra(0, n, &edges_list));
+            for v in 0..n {
+                match dists[v] {
+                    Some(d) => assert_eq!(counts[v], walks[d][v].0),
+                    None => assert_eq!(counts[v], 0),
+                }
+                let k = 1 + rng.rand(6);
+                let naive: Vec<usize> = (0..=max_len)
+                    .flat_map(|len| std::iter::repeat_n(len, walks[len][v].1))
+                    .take(k)
+                    .collect();
+                let result = k_shortest_walks(0, v, k, &edges_list);
+                // walks longer than max_len are not counted by the naive one
+                assert!(result.len() >= naive.len());
+                assert_eq!(result[..naive.len()], naive[..]);
+                if naive.len() < k {
+                    assert!(result[naive.len()..].iter().all(|&len| len > max_len));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_csr_graph_with_edge_lists() {
+        let mut rng = Rng::new(41);
+        for _ in 0..50 {
+            let n = 1 + rng.rand(10);
+            let edges: Vec<(usize, usize, usize)> = (0..rng.rand(20))
+                .map(|_| (rng.rand(n), rng.rand(n), rng.rand(10)))
+                .collect();
+            for directed in [true, false] {
+                let graph = Graph::from_edges(n, &edges, directed);
+                let mut edges_list: Vec<Vec<Edge>> = vec![vec![]; n];
+                for &(u, v, cost) in &edges {
+                    edges_list[u].push(Edge { to: v, cost });
+                    if !directed {
+                        edges_list[v].push(Edge { to: u, cost });
+                    }
+                }
+                assert_eq!(graph.len(), n);
+                assert_eq!(graph.is_directed(), directed);
+                let mut csr_edges = graph.to_edges_list();
+                for v in 0..n {
+                    csr_edges[v].sort_by_key(|e| (e.to, e.cost));
+                    edges_list[v].sort_by_key(|e| (e.to, e.cost));
+                    assert_eq!(
+                        csr_edges[v]
+                            .iter()
+                            .map(|e| (e.to, e.cost))
+                            .collect::<Vec<_>>(),
+                        edges_list[v]
+                            .iter()
+                            .map(|e| (e.to, e.cost))
+                            .collect::<Vec<_>>()
+                    );
+                }
+                for s in 0..n {
+                    assert_eq!(graph.dijkstra(s), dijkstra(s, n, &edges_list));
+                }
+                let adjacency_list = graph.to_adjacency_list();
+                assert_eq!(
+                    graph.strongly_connected_component(),
+                    adjacency_list.strongly_connected_component()
+                );
+                assert_eq!(graph.topological_sort(), adjacency_list.topological_sort());
+                if !directed {
+                    assert_eq!(
+                        graph.checked_bi_partition(),
+                        adjacency_list.checked_bi_partition()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_csr_graph_eulerian_trail_follows_direction() {
+        let edges = [(1, 2), (2, 3), (3, 1), (1, 1)];
+        let undirected = Graph::from_atcoder_tuples(3, &edges, false);
+        let trail = undirected.eulerian_trail().unwrap();
+        assert_eq!(trail.len(), edges.len() + 1);
+        assert_eq!(
+            Some(trail),
+            undirected.to_adjacency_list().undirected_eulerian_trail()
+        );
+        let directed = Graph::from_atcoder_tuples(3, &edges, true);
+        assert_eq!(
+            directed.eulerian_trail(),
+            directed.to_adjacency_list().eulerian_trail()
+        );
+        assert_eq!(directed.eulerian_trail().unwrap().len(), edges.len() + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "graph must be undirected")]
+    fn test_csr_graph_bipartition_rejects_directed() {
+        let graph = Graph::from_atcoder_tuples(3, &[(1, 2), (2, 3)], true);
+        graph.checked_bi_partition();
+    }
 }
 
+use crate::bipartite_matching::HopcroftKarp;
 use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, VecDeque};
+use std::iter::Copied;
+use std::slice::Iter;
 
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Edge {
@@ -202,8 +636,16 @@ pub struct Edge {
 struct Pair(usize, usize);
 
 pub fn dijkstra(s: usize, max_v: usize, edges_list: &[Vec<Edge>]) -> Vec<Option<usize>> {
+    shortest_distances(s, max_v, |v| edges_list[v].iter().map(|e| (e.to, e.cost)))
+}
+
+/// Dijkstra over (to, cost) of edges from each vertex
+fn shortest_distances<I>(s: usize, n: usize, edges: impl Fn(usize) -> I) -> Vec<Option<usize>>
+where
+    I: Iterator<Item = (usize, usize)>,
+{
     let mut que = BinaryHeap::new();
-    let mut min_dists: Vec<Option<usize>> = (0..max_v).map(|_| None).collect();
+    let mut min_dists: Vec<Option<usize>> = vec![None; n];
     min_dists[s] = Some(0);
     que.push(Reverse(Pair(0, s)));
     while let Some(Reverse(p)) = que.pop() {
@@ -211,24 +653,193 @@ pub fn dijkstra(s: usize, max_v: usize, edges_list: &[Vec<Edge>]) -> Vec<Option<
         if min_dists[v].unwrap() < p.0 {
             continue;
         };
-        for e in &edges_list[v] {
-            let candidate_dist = min_dists[v].unwrap() + e.cost;
-            if candidate_dist < min_dists[e.to].unwrap_or(usize::MAX) {
-                min_dists[e.to] = Some(candidate_dist);
-                que.push(Reverse(Pair(candidate_dist, e.to)));
+        for (to, cost) in edges(v) {
+            let candidate_dist = min_dists[v].unwrap() + cost;
+            if candidate_dist < min_dists[to].unwrap_or(usize::MAX) {
+                min_dists[to] = Some(candidate_dist);
+                que.push(Reverse(Pair(candidate_dist, to)));
             }
         }
     }
     min_dists
 }
 
+/// Shortest distances from s and the number of shortest paths modulo modulo.
+/// Costs must be positive, since zero-cost cycles make the number infinite.
+/// ```
+/// use competitive_tools_rust::graph::{dijkstra_with_count, Edge};
+/// // 0 -> 1 -> 3, 0 -> 2 -> 3, all costs 1
+/// let edges_list = vec![
+///     vec![Edge { to: 1, cost: 1 }, Edge { to: 2, cost: 1 }],
+///     vec![Edge { to: 3, cost: 1 }],
+///     vec![Edge { to: 3, cost: 1 }],
+///     vec![],
+///     vec![],
+/// ];
+/// let (dists, counts) = dijkstra_with_count(0, 5, &edges_list, 1_000_000_007);
+/// assert_eq!(dists, vec![Some(0), Some(1), Some(1), Some(2), None]);
+/// assert_eq!(counts, vec![1, 1, 1, 2, 0]);
+/// ```
+pub fn dijkstra_with_count(
+    s: usize,
+    max_v: usize,
+    edges_list: &[Vec<Edge>],
+    modulo: usize,
+) -> (Vec<Option<usize>>, Vec<usize>) {
+    let mut que = BinaryHeap::new();
+    let mut min_dists: Vec<Option<usize>> = vec![None; max_v];
+    let mut counts = vec![0; max_v];
+    min_dists[s] = Some(0);
+    counts[s] = 1 % modulo;
+    que.push(Reverse(Pair(0, s)));
+    while let Some(Reverse(Pair(dist, v))) = que.pop() {
+        if min_dists[v].unwrap() < dist {
+            continue;
+        }
+        for e in &edges_list[v] {
+            let candidate_dist = dist + e.cost;
+            match min_dists[e.to] {
+                Some(to_dist) if to_dist < candidate_dist => {}
+                Some(to_dist) if to_dist == candidate_dist => {
+                    counts[e.to] = (counts[e.to] + counts[v]) % modulo;
+                }
+                _ => {
+                    min_dists[e.to] = Some(candidate_dist);
+                    counts[e.to] = counts[v];
+                    que.push(Reverse(Pair(candidate_dist, e.to)));
+                }
+            }
+        }
+    }
+    (min_dists, counts)
+}
+
+/// Lengths of the k shortest walks from s to t in ascending order
+/// (vertexes and edges may be repeated), fewer if there are not so many walks.
+/// Each vertex is popped at most k times, so it runs in O(k E log(k E)).
+/// ```
+/// use competitive_tools_rust::graph::{k_shortest_walks, Edge};
+/// // 0 -> 1 (1), 0 -> 1 (4), 1 -> 0 (2)
+/// let edges_list = vec![
+///     vec![Edge { to: 1, cost: 1 }, Edge { to: 1, cost: 4 }],
+///     vec![Edge { to: 0, cost: 2 }],
+/// ];
+/// assert_eq!(k_shortest_walks(0, 1, 4, &edges_list), vec![1, 4, 4, 7]);
+/// assert_eq!(k_shortest_walks(1, 1, 3, &edges_list), vec![0, 3, 6]);
+/// assert_eq!(k_shortest_walks(0, 1, 2, &[vec![], vec![]]), vec![]);
+/// ```
+pub fn k_shortest_walks(s: usize, t: usize, k: usize, edges_list: &[Vec<Edge>]) -> Vec<usize> {
+    let mut que = BinaryHeap::new();
+    let mut popped = vec![0; edges_list.len()];
+    let mut lengths = vec![];
+    que.push(Reverse(Pair(0, s)));
+    while let Some(Reverse(Pair(dist, v))) = que.pop() {
+        if popped[v] == k {
+            continue;
+        }
+        popped[v] += 1;
+        if v == t {
+            lengths.push(dist);
+            if lengths.len() == k {
+                break;
+            }
+        }
+        for e in &edges_list[v] {
+            if popped[e.to] < k {
+                que.push(Reverse(Pair(dist + e.cost, e.to)));
+            }
+        }
+    }
+    lengths
+}
+
+/// 2-coloring of a bipartite graph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bipartition {
+    /// true for side x, false for side y
+    /// The smallest vertex of each component is in x.
+    pub side: Vec<bool>,
+    /// Connected component id of each vertex, numbered from the smallest vertex
+    pub component: Vec<usize>,
+    /// (number of vertexes in x, number of vertexes in y) of each component
+    pub sizes: Vec<(usize, usize)>,
+}
+
 pub trait BipartiteGraph {
     fn bi_partition(&self) -> (Vec<usize>, Vec<usize>);
+    fn checked_bi_partition(&self) -> Option<Bipartition>;
+    fn odd_cycle(&self) -> Option<Vec<usize>>;
+    fn maximum_matching(&self) -> Vec<(usize, usize)>;
+}
+
+/// Color every component by BFS, or return an odd cycle
+fn two_coloring<I>(n: usize, adjacent: impl Fn(usize) -> I) -> Result<Bipartition, Vec<usize>>
+where
+    I: Iterator<Item = usize>,
+{
+    let mut side: Vec<Option<bool>> = vec![None; n];
+    let mut parent: Vec<Option<usize>> = vec![None; n];
+    let mut component = vec![0; n];
+    let mut sizes = vec![];
+    for start in 0..n {
+        if side[start].is_some() {
+            continue;
+        }
+        let id = sizes.len();
+        let mut size = (0, 0);
+        side[start] = Some(true);
+        let mut que = VecDeque::from(vec![start]);
+        while let Some(v) = que.pop_front() {
+            component[v] = id;
+            let bi = side[v].unwrap();
+            if bi {
+                size.0 += 1;
+            } else {
+                size.1 += 1;
+            }
+            for to in adjacent(v) {
+                match side[to] {
+                    None => {
+                        side[to] = Some(!bi);
+                        parent[to] = Some(v);
+                        que.push_back(to);
+                    }
+                    Some(to_bi) if to_bi == bi => {
+                        // both ends have the same depth in the BFS tree
+                        let mut from_v = vec![v];
+                        let mut from_to = vec![to];
+                        let (mut a, mut b) = (v, to);
+                        while a != b {
+                            a = parent[a].unwrap();
+                            b = parent[b].unwrap();
+                            from_v.push(a);
+                            from_to.push(b);
+                        }
+                        from_to.pop();
+                        from_v.extend(from_to.into_iter().rev());
+                        return Err(from_v);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        sizes.push(size);
+    }
+    Ok(Bipartition {
+        side: side.into_iter().map(|bi| bi.unwrap()).collect(),
+        component,
+        sizes,
+    })
 }
 
 impl BipartiteGraph for Vec<Vec<usize>> {
     ///
     /// Partition bipartite graph into two vertex ids.
+    /// Every connected component is colored.
+    ///
+    /// # Panics
+    /// Panics if the graph is not bipartite.
+    /// Use `checked_bi_partition` or `odd_cycle` when that may happen.
     /// ```
     /// use competitive_tools_rust::graph::BipartiteGraph;
     /// // 0
@@ -262,29 +873,867 @@ impl BipartiteGraph for Vec<Vec<usize>> {
     /// ];
     /// assert_eq!(adjacency_list.bi_partition(), (vec![0, 3, 4, 5], vec![1, 2]));
     /// ```
+    /// ```should_panic
+    /// use competitive_tools_rust::graph::BipartiteGraph;
+    /// // triangle
+    /// let adjacency_list: Vec<Vec<usize>> = vec![vec![1, 2], vec![0, 2], vec![0, 1]];
+    /// adjacency_list.bi_partition();
+    /// ```
     fn bi_partition(&self) -> (Vec<usize>, Vec<usize>) {
-        let mut current: Vec<Option<bool>> = vec![None; self.len()];
-        let mut stack: Vec<(usize, bool)> = vec![];
-        current[0] = Some(true);
-        stack.append(&mut self[0].iter().map(|&i| (i, false)).collect());
-
-        while let Some((vertex_id, bi)) = stack.pop() {
-            current[vertex_id] = Some(bi);
-            let nexts = self[vertex_id]
-                .iter()
-                .filter(|&&i| current[i].is_none())
-                .map(|&i| (i, !bi));
-            stack.append(&mut nexts.collect());
-        }
-        let mut x: Vec<usize> = vec![];
-        let mut y: Vec<usize> = vec![];
-        for (ind, bi) in current.iter().enumerate() {
-            if bi.unwrap() {
-                x.push(ind);
+        bi_partition(self.len(), adjacent_in(self))
+    }
+
+    /// Color every connected component,
+    /// or return None when the graph has an odd cycle
+    /// ```
+    /// use competitive_tools_rust::graph::{Bipartition, BipartiteGraph};
+    /// // 0 - 1   2   3 - 4
+    /// //             |
+    /// //             5
+    /// let adjacency_list: Vec<Vec<usize>> = vec![
+    ///     vec![1],
+    ///     vec![0],
+    ///     vec![],
+    ///     vec![4, 5],
+    ///     vec![3],
+    ///     vec![3],
+    /// ];
+    /// assert_eq!(adjacency_list.checked_bi_partition(), Some(Bipartition {
+    ///     side: vec![true, false, true, true, false, false],
+    ///     component: vec![0, 0, 1, 2, 2, 2],
+    ///     sizes: vec![(1, 1), (1, 0), (1, 2)],
+    /// }));
+    /// // triangle
+    /// let adjacency_list: Vec<Vec<usize>> = vec![vec![1, 2], vec![0, 2], vec![0, 1]];
+    /// assert_eq!(adjacency_list.checked_bi_partition(), None);
+    /// let empty: Vec<Vec<usize>> = vec![];
+    /// assert_eq!(empty.bi_partition(), (vec![], vec![]));
+    /// ```
+    fn checked_bi_partition(&self) -> Option<Bipartition> {
+        two_coloring(self.len(), adjacent_in(self)).ok()
+    }
+
+    /// An odd cycle as the list of vertexes in the order of the cycle,
+    /// which proves that the graph is not bipartite
+    /// ```
+    /// use competitive_tools_rust::graph::BipartiteGraph;
+    /// // 0 - 1 - 2
+    /// //     |   |
+    /// //     4 - 3
+    /// //     |
+    /// //     5 - 6
+    /// let adjacency_list: Vec<Vec<usize>> = vec![
+    ///     vec![1],
+    ///     vec![0, 2, 4],
+    ///     vec![1, 3],
+    ///     vec![2, 4],
+    ///     vec![1, 3, 5],
+    ///     vec![4, 6],
+    ///     vec![5],
+    /// ];
+    /// assert_eq!(adjacency_list.odd_cycle(), None);
+    /// let mut adjacency_list = adjacency_list;
+    /// adjacency_list[4].push(6);
+    /// adjacency_list[6].push(4);
+    /// assert_eq!(adjacency_list.odd_cycle(), Some(vec![5, 4, 6]));
+    /// ```
+    fn odd_cycle(&self) -> Option<Vec<usize>> {
+        two_coloring(self.len(), adjacent_in(self)).err()
+    }
+
+    /// Maximum matching by Hopcroft-Karp on the partition of bi_partition.
+    /// Returns pairs of (vertex in x, vertex in y).
+    ///
+    /// # Panics
+    /// Panics if the graph is not bipartite, same as bi_partition.
+    /// ```
+    /// use competitive_tools_rust::graph::BipartiteGraph;
+    /// // 0 - 1 - 2 - 3
+    /// let adjacency_list: Vec<Vec<usize>> = vec![vec![1], vec![0, 2], vec![1, 3], vec![2]];
+    /// assert_eq!(adjacency_list.maximum_matching(), vec![(0, 1), (2, 3)]);
+    /// ```
+    fn maximum_matching(&self) -> Vec<(usize, usize)> {
+        maximum_matching(self.len(), adjacent_in(self))
+    }
+}
+
+fn bi_partition<I>(n: usize, adjacent: impl Fn(usize) -> I) -> (Vec<usize>, Vec<usize>)
+where
+    I: Iterator<Item = usize>,
+{
+    let bipartition = two_coloring(n, adjacent).expect("graph is not bipartite");
+    let mut x: Vec<usize> = vec![];
+    let mut y: Vec<usize> = vec![];
+    for (ind, &bi) in bipartition.side.iter().enumerate() {
+        if bi {
+            x.push(ind);
+        } else {
+            y.push(ind);
+        }
+    }
+    (x, y)
+}
+
+fn maximum_matching<I>(n: usize, adjacent: impl Fn(usize) -> I) -> Vec<(usize, usize)>
+where
+    I: Iterator<Item = usize>,
+{
+    let (x, y) = bi_partition(n, &adjacent);
+    let mut index = vec![0; n];
+    x.iter().enumerate().for_each(|(i, &v)| index[v] = i);
+    y.iter().enumerate().for_each(|(i, &v)| index[v] = i);
+    let mut hk = HopcroftKarp::new(x.len(), y.len());
+    for (i, &v) in x.iter().enumerate() {
+        for to in adjacent(v) {
+            hk.add_edge(i, index[to]);
+        }
+    }
+    hk.maximum_matching()
+        .into_iter()
+        .map(|(l, r)| (x[l], y[r]))
+        .collect()
+}
+
+/// Exponential algorithms for small undirected graphs,
+/// where each edge appears in the lists of both ends and self loops are ignored.
+/// 2-colorability is the special case checked by `BipartiteGraph`.
+pub trait SmallGraph {
+    fn chromatic_number(&self) -> usize;
+    fn maximum_independent_set(&self) -> Vec<usize>;
+    fn maximum_clique(&self) -> Vec<usize>;
+}
+
+/// Neighbors of each vertex as bitmasks, without self loops
+fn adjacency_masks<I>(n: usize, adjacent: impl Fn(usize) -> I) -> Vec<u64>
+where
+    I: Iterator<Item = usize>,
+{
+    assert!(n <= 64, "graph must have at most 64 vertexes");
+    (0..n)
+        .map(|v| {
+            adjacent(v)
+                .filter(|&to| to != v)
+                .fold(0, |mask, to| mask | 1 << to)
+        })
+        .collect()
+}
+
+/// Maximum independent set among remaining by branching on high degree vertexes in O(1.38^n)
+fn independent_set_in(adjacency: &[u64], remaining: u64) -> u64 {
+    if remaining == 0 {
+        return 0;
+    }
+    let vertexes = (0..adjacency.len()).filter(|&v| remaining >> v & 1 == 1);
+    let degree = |v: usize| (adjacency[v] & remaining).count_ones();
+    let (max_degree, v) = vertexes.map(|v| (degree(v), v)).max().unwrap();
+    if max_degree <= 2 {
+        // paths and cycles: take a vertex of minimum degree greedily
+        let v = (0..adjacency.len())
+            .filter(|&v| remaining >> v & 1 == 1)
+            .min_by_key(|&v| degree(v))
+            .unwrap();
+        return 1 << v | independent_set_in(adjacency, remaining & !(adjacency[v] | 1 << v));
+    }
+    let with_v = 1 << v | independent_set_in(adjacency, remaining & !(adjacency[v] | 1 << v));
+    let without_v = independent_set_in(adjacency, remaining & !(1 << v));
+    if with_v.count_ones() >= without_v.count_ones() {
+        with_v
+    } else {
+        without_v
+    }
+}
+
+fn mask_to_vertexes(mask: u64) -> Vec<usize> {
+    (0..64).filter(|&v| mask >> v & 1 == 1).collect()
+}
+
+impl SmallGraph for Vec<Vec<usize>> {
+    /// Minimum number of colors such that adjacent vertexes have different colors,
+    /// by counting k-colorings with inclusion-exclusion in O(2^n n) time and O(2^n) memory.
+    /// The counts are taken modulo 1e9+7, so the answer is only correct with high probability:
+    /// k is missed if the number of k-colorings happens to be a multiple of the prime.
+    ///
+    /// # Panics
+    /// Panics if the graph has more than 25 vertexes.
+    /// ```
+    /// use competitive_tools_rust::graph::SmallGraph;
+    /// // 5-cycle
+    /// let graph = vec![vec![1, 4], vec![0, 2], vec![1, 3], vec![2, 4], vec![3, 0]];
+    /// assert_eq!(graph.chromatic_number(), 3);
+    /// let graph: Vec<Vec<usize>> = vec![vec![], vec![]];
+    /// assert_eq!(graph.chromatic_number(), 1);
+    /// ```
+    fn chromatic_number(&self) -> usize {
+        chromatic_number(&adjacency_masks(self.len(), adjacent_in(self)))
+    }
+
+    /// Maximum independent set in ascending order (n <= 50 or so)
+    /// ```
+    /// use competitive_tools_rust::graph::SmallGraph;
+    /// // 0 - 1 - 2 - 3
+    /// //     |
+    /// //     4
+    /// let graph = vec![vec![1], vec![0, 2, 4], vec![1, 3], vec![2], vec![1]];
+    /// assert_eq!(graph.maximum_independent_set(), vec![0, 2, 4]);
+    /// ```
+    fn maximum_independent_set(&self) -> Vec<usize> {
+        maximum_independent_set(&adjacency_masks(self.len(), adjacent_in(self)))
+    }
+
+    /// Maximum clique in ascending order (n <= 50 or so)
+    /// ```
+    /// use competitive_tools_rust::graph::SmallGraph;
+    /// // triangle 1, 2, 3 with a pendant 0
+    /// let graph = vec![vec![1], vec![0, 2, 3], vec![1, 3], vec![1, 2]];
+    /// assert_eq!(graph.maximum_clique(), vec![1, 2, 3]);
+    /// ```
+    fn maximum_clique(&self) -> Vec<usize> {
+        maximum_clique(&adjacency_masks(self.len(), adjacent_in(self)))
+    }
+}
+
+fn chromatic_number(adjacency: &[u64]) -> usize {
+    const MOD: usize = 1_000_000_007;
+    let n = adjacency.len();
+    assert!(n <= 25, "chromatic_number supports at most 25 vertexes");
+    if n == 0 {
+        return 0;
+    }
+    // independent[mask]: number of independent sets (including the empty one) in mask
+    let mut independent = vec![0; 1 << n];
+    independent[0] = 1;
+    for mask in 1..1usize << n {
+        let v = mask.trailing_zeros() as usize;
+        let without_v = mask & !(1 << v);
+        independent[mask] =
+            (independent[without_v] + independent[without_v & !adjacency[v] as usize]) % MOD;
+    }
+    let mut power = vec![1; 1 << n];
+    for k in 1..n {
+        // number of ways to cover all vertexes by k independent sets
+        let mut covers = 0;
+        for mask in 0..1usize << n {
+            power[mask] = power[mask] * independent[mask] % MOD;
+            if (n - mask.count_ones() as usize).is_multiple_of(2) {
+                covers += power[mask];
             } else {
-                y.push(ind);
+                covers += MOD - power[mask];
+            }
+        }
+        if covers % MOD != 0 {
+            return k;
+        }
+    }
+    n
+}
+
+fn maximum_independent_set(adjacency: &[u64]) -> Vec<usize> {
+    let all = (0..adjacency.len()).fold(0, |mask, v| mask | 1 << v);
+    mask_to_vertexes(independent_set_in(adjacency, all))
+}
+
+fn maximum_clique(adjacency: &[u64]) -> Vec<usize> {
+    let all = (0..adjacency.len()).fold(0, |mask, v| mask | 1 << v);
+    let complement: Vec<u64> = adjacency
+        .iter()
+        .enumerate()
+        .map(|(v, &mask)| all & !mask & !(1 << v))
+        .collect();
+    mask_to_vertexes(independent_set_in(&complement, all))
+}
+
+pub trait Rerooting {
+    fn rerooting<T, M, A>(&self, identity: T, merge: M, add_root: A) -> Vec<T>
+    where
+        T: Clone,
+        M: Fn(&T, &T) -> T,
+        A: Fn(&T, usize) -> T;
+}
+
+impl Rerooting for Vec<Vec<usize>> {
+    /// Tree DP for all roots in O(n).
+    /// self is an undirected tree (or forest).
+    /// The DP value of the subtree rooted at v is
+    /// add_root(merge of DP values of the children of v, v),
+    /// and the returned value of v is that value when v is the root of the whole tree.
+    /// merge must be associative and identity must be its identity element.
+    /// ```
+    /// use competitive_tools_rust::graph::Rerooting;
+    /// // 0 - 1 - 2
+    /// //     |
+    /// //     3 - 4
+    /// let tree: Vec<Vec<usize>> = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    ///
+    /// // sum of distances: (number of vertexes, sum of (distance + 1))
+    /// let dp = tree.rerooting(
+    ///     (0, 0),
+    ///     |a: &(usize, usize), b: &(usize, usize)| (a.0 + b.0, a.1 + b.1),
+    ///     |acc, _| (acc.0 + 1, acc.1 + acc.0 + 1),
+    /// );
+    /// let sum_of_distances: Vec<usize> = dp.iter().map(|&(n, s)| s - n).collect();
+    /// assert_eq!(sum_of_distances, vec![8, 5, 8, 6, 9]);
+    ///
+    /// // farthest distance: (max distance + 1)
+    /// let dp = tree.rerooting(0, |a: &usize, b: &usize| *a.max(b), |acc, _| acc + 1);
+    /// let farthest: Vec<usize> = dp.iter().map(|d| d - 1).collect();
+    /// assert_eq!(farthest, vec![3, 2, 3, 2, 3]);
+    /// ```
+    fn rerooting<T, M, A>(&self, identity: T, merge: M, add_root: A) -> Vec<T>
+    where
+        T: Clone,
+        M: Fn(&T, &T) -> T,
+        A: Fn(&T, usize) -> T,
+    {
+        rerooting(self.len(), adjacent_in(self), identity, merge, add_root)
+    }
+}
+
+fn rerooting<I, T, M, A>(
+    n: usize,
+    adjacent: impl Fn(usize) -> I,
+    identity: T,
+    merge: M,
+    add_root: A,
+) -> Vec<T>
+where
+    I: Iterator<Item = usize>,
+    T: Clone,
+    M: Fn(&T, &T) -> T,
+    A: Fn(&T, usize) -> T,
+{
+    let mut parent: Vec<Option<usize>> = vec![None; n];
+    let mut seen = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    for root in 0..n {
+        if seen[root] {
+            continue;
+        }
+        seen[root] = true;
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            for to in adjacent(v) {
+                if !seen[to] {
+                    seen[to] = true;
+                    parent[to] = Some(v);
+                    stack.push(to);
+                }
+            }
+        }
+    }
+
+    // bottom-up: DP values of subtrees hanging below each vertex
+    let mut down: Vec<T> = vec![identity.clone(); n];
+    for &v in order.iter().rev() {
+        let acc = adjacent(v)
+            .filter(|&to| parent[to] == Some(v))
+            .fold(identity.clone(), |acc, to| merge(&acc, &down[to]));
+        down[v] = add_root(&acc, v);
+    }
+
+    // top-down: up[v] is the DP value of the rest of the tree seen from v
+    let mut up: Vec<T> = vec![identity.clone(); n];
+    let mut result: Vec<T> = vec![identity.clone(); n];
+    for &v in &order {
+        let values: Vec<T> = adjacent(v)
+            .map(|to| {
+                if parent[to] == Some(v) {
+                    down[to].clone()
+                } else {
+                    up[v].clone()
+                }
+            })
+            .collect();
+        let mut suffix = vec![identity.clone(); values.len() + 1];
+        for i in (0..values.len()).rev() {
+            suffix[i] = merge(&values[i], &suffix[i + 1]);
+        }
+        result[v] = add_root(&suffix[0], v);
+        let mut prefix = identity.clone();
+        for (i, to) in adjacent(v).enumerate() {
+            if parent[to] == Some(v) {
+                up[to] = add_root(&merge(&prefix, &suffix[i + 1]), v);
+            }
+            prefix = merge(&prefix, &values[i]);
+        }
+    }
+    result
+}
+
+/// Hierholzer's algorithm without recursion from start.
+/// adjacency\[v\] is the list of (to, edge id).
+/// Returns None when some edges are not reachable from start.
+fn hierholzer(
+    adjacency: &[Vec<(usize, usize)>],
+    m: usize,
+    start: usize,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    let mut used = vec![false; m];
+    let mut next = vec![0; adjacency.len()];
+    let mut stack: Vec<(usize, Option<usize>)> = vec![(start, None)];
+    let mut vertexes = Vec::with_capacity(m + 1);
+    let mut edge_ids = Vec::with_capacity(m);
+    while let Some(&(v, arrived_by)) = stack.last() {
+        while next[v] < adjacency[v].len() && used[adjacency[v][next[v]].1] {
+            next[v] += 1;
+        }
+        if let Some(&(to, id)) = adjacency[v].get(next[v]) {
+            used[id] = true;
+            stack.push((to, Some(id)));
+        } else {
+            stack.pop();
+            vertexes.push(v);
+            if let Some(id) = arrived_by {
+                edge_ids.push(id);
             }
         }
-        (x, y)
+    }
+    if edge_ids.len() < m {
+        return None;
+    }
+    vertexes.reverse();
+    edge_ids.reverse();
+    Some((vertexes, edge_ids))
+}
+
+/// Eulerian trail (circuit if exists) of directed graph with n vertexes and edges (from, to).
+/// Returns (vertex sequence, edge id sequence), where edge ids are indexes of edges.
+/// Returns empty sequences when there are no edges.
+/// ```
+/// use competitive_tools_rust::graph::directed_eulerian_trail;
+/// // 0 -> 1 -> 2 -> 0 -> 3
+/// let edges = vec![(0, 1), (2, 0), (1, 2), (0, 3)];
+/// assert_eq!(directed_eulerian_trail(4, &edges), Some((vec![0, 1, 2, 0, 3], vec![0, 2, 1, 3])));
+/// // circuit
+/// let edges = vec![(1, 2), (2, 1)];
+/// assert_eq!(directed_eulerian_trail(3, &edges), Some((vec![1, 2, 1], vec![0, 1])));
+/// // in-degree of 1 is too large
+/// assert_eq!(directed_eulerian_trail(3, &[(0, 1), (2, 1)]), None);
+/// // not connected
+/// assert_eq!(directed_eulerian_trail(4, &[(0, 1), (1, 0), (2, 3), (3, 2)]), None);
+/// ```
+pub fn directed_eulerian_trail(
+    n: usize,
+    edges: &[(usize, usize)],
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    if edges.is_empty() {
+        return Some((vec![], vec![]));
+    }
+    let mut adjacency = vec![vec![]; n];
+    let mut balance = vec![0isize; n];
+    for (id, &(from, to)) in edges.iter().enumerate() {
+        adjacency[from].push((to, id));
+        balance[from] += 1;
+        balance[to] -= 1;
+    }
+    let starts: Vec<usize> = (0..n).filter(|&v| balance[v] == 1).collect();
+    let ends = (0..n).filter(|&v| balance[v] == -1).count();
+    if balance.iter().any(|b| b.abs() > 1) || starts.len() != ends || starts.len() > 1 {
+        return None;
+    }
+    let start = match starts.first() {
+        Some(&start) => start,
+        None => (0..n).find(|&v| !adjacency[v].is_empty()).unwrap(),
+    };
+    hierholzer(&adjacency, edges.len(), start)
+}
+
+/// Eulerian trail (circuit if exists) of undirected graph with n vertexes and edges (u, v).
+/// Returns (vertex sequence, edge id sequence), where edge ids are indexes of edges.
+/// Returns empty sequences when there are no edges.
+/// ```
+/// use competitive_tools_rust::graph::undirected_eulerian_trail;
+/// // 0 - 1
+/// // | / |
+/// // 2 - 3
+/// let edges = vec![(0, 1), (1, 3), (3, 2), (2, 0), (1, 2)];
+/// let (vertexes, edge_ids) = undirected_eulerian_trail(4, &edges).unwrap();
+/// assert_eq!(vertexes, vec![1, 0, 2, 3, 1, 2]);
+/// assert_eq!(edge_ids, vec![0, 3, 2, 1, 4]);
+/// // three vertexes of odd degree
+/// assert_eq!(undirected_eulerian_trail(4, &[(0, 1), (0, 2), (0, 3)]), None);
+/// ```
+pub fn undirected_eulerian_trail(
+    n: usize,
+    edges: &[(usize, usize)],
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    if edges.is_empty() {
+        return Some((vec![], vec![]));
+    }
+    let mut adjacency = vec![vec![]; n];
+    for (id, &(u, v)) in edges.iter().enumerate() {
+        adjacency[u].push((v, id));
+        adjacency[v].push((u, id));
+    }
+    let mut degree = vec![0; n];
+    for &(u, v) in edges {
+        degree[u] += 1;
+        degree[v] += 1;
+    }
+    let odds: Vec<usize> = (0..n).filter(|&v| degree[v] % 2 == 1).collect();
+    if odds.len() > 2 {
+        return None;
+    }
+    let start = match odds.first() {
+        Some(&start) => start,
+        None => (0..n).find(|&v| !adjacency[v].is_empty()).unwrap(),
+    };
+    hierholzer(&adjacency, edges.len(), start)
+}
+
+pub trait EulerianTrail {
+    fn eulerian_trail(&self) -> Option<Vec<usize>>;
+    fn undirected_eulerian_trail(&self) -> Option<Vec<usize>>;
+}
+
+impl EulerianTrail for Vec<Vec<usize>> {
+    /// Vertex sequence of an Eulerian trail (circuit if exists) of directed graph
+    /// ```
+    /// use competitive_tools_rust::graph::EulerianTrail;
+    /// // 0 -> 1 -> 2
+    /// // ^         |
+    /// // +---------+
+    /// let adjacency_list: Vec<Vec<usize>> = vec![vec![1], vec![2], vec![0]];
+    /// assert_eq!(adjacency_list.eulerian_trail(), Some(vec![0, 1, 2, 0]));
+    /// ```
+    fn eulerian_trail(&self) -> Option<Vec<usize>> {
+        let edges = directed_edges(self.len(), adjacent_in(self));
+        directed_eulerian_trail(self.len(), &edges).map(|(vertexes, _)| vertexes)
+    }
+
+    /// Vertex sequence of an Eulerian trail (circuit if exists) of undirected graph,
+    /// where each edge appears in the lists of both ends (a self loop appears twice)
+    /// ```
+    /// use competitive_tools_rust::graph::EulerianTrail;
+    /// // 0 - 1 - 2
+    /// //     |   |
+    /// //     4 - 3
+    /// let adjacency_list: Vec<Vec<usize>> = vec![
+    ///     vec![1],
+    ///     vec![0, 2, 4],
+    ///     vec![1, 3],
+    ///     vec![2, 4],
+    ///     vec![1, 3],
+    /// ];
+    /// assert_eq!(adjacency_list.undirected_eulerian_trail(), Some(vec![0, 1, 2, 3, 4, 1]));
+    /// ```
+    fn undirected_eulerian_trail(&self) -> Option<Vec<usize>> {
+        let edges = undirected_edges(self.len(), adjacent_in(self));
+        undirected_eulerian_trail(self.len(), &edges).map(|(vertexes, _)| vertexes)
+    }
+}
+
+/// (from, to) of every edge
+fn directed_edges<I>(n: usize, adjacent: impl Fn(usize) -> I) -> Vec<(usize, usize)>
+where
+    I: Iterator<Item = usize>,
+{
+    (0..n)
+        .flat_map(|from| adjacent(from).map(move |to| (from, to)))
+        .collect()
+}
+
+/// (u, v) of every edge stored in the lists of both ends, counting each once
+fn undirected_edges<I>(n: usize, adjacent: impl Fn(usize) -> I) -> Vec<(usize, usize)>
+where
+    I: Iterator<Item = usize>,
+{
+    let mut edges: Vec<(usize, usize)> = vec![];
+    for u in 0..n {
+        edges.extend(adjacent(u).filter(|&v| u < v).map(|v| (u, v)));
+        let loops = adjacent(u).filter(|&v| u == v).count();
+        edges.extend((0..loops / 2).map(|_| (u, u)));
+    }
+    edges
+}
+
+/// Weight of edges of Graph
+pub trait Weight: Copy {
+    fn cost(&self) -> usize;
+}
+
+/// Unit weight
+impl Weight for () {
+    fn cost(&self) -> usize {
+        1
+    }
+}
+
+impl Weight for usize {
+    fn cost(&self) -> usize {
+        *self
+    }
+}
+
+/// Directed or undirected graph in Compressed Sparse Row format.
+/// W is `()` for unweighted graphs and `usize` for weighted graphs.
+/// Edges from v are stored contiguously in the order they are given,
+/// and an undirected edge is stored in both directions like `Vec<Vec<usize>>` adjacency lists.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graph<W> {
+    /// Edges from v are targets\[start\[v\]..start\[v + 1\]\]
+    start: Vec<usize>,
+    targets: Vec<(usize, W)>,
+    directed: bool,
+}
+
+impl Graph<()> {
+    /// Unweighted graph from 1-indexed edges
+    /// ```
+    /// use competitive_tools_rust::graph::Graph;
+    /// let graph = Graph::from_atcoder_tuples(3, &[(1, 2), (2, 3)], false);
+    /// assert_eq!(graph.adjacent(1).collect::<Vec<_>>(), vec![0, 2]);
+    /// assert_eq!(graph.to_adjacency_list(), vec![vec![1], vec![0, 2], vec![1]]);
+    /// ```
+    pub fn from_atcoder_tuples(n: usize, tuples: &[(usize, usize)], directed: bool) -> Self {
+        let edges: Vec<(usize, usize, ())> =
+            tuples.iter().map(|&(u, v)| (u - 1, v - 1, ())).collect();
+        Graph::from_edges(n, &edges, directed)
+    }
+}
+
+impl Graph<usize> {
+    /// Weighted graph from 1-indexed edges (from, to, weight)
+    /// ```
+    /// use competitive_tools_rust::graph::Graph;
+    /// let graph = Graph::from_atcoder_weighted_tuples(3, &[(1, 2, 5), (2, 3, 7)], true);
+    /// assert_eq!(graph.edges_from(1), &[(2, 7)]);
+    /// assert_eq!(graph.dijkstra(0), vec![Some(0), Some(5), Some(12)]);
+    /// ```
+    pub fn from_atcoder_weighted_tuples(
+        n: usize,
+        tuples: &[(usize, usize, usize)],
+        directed: bool,
+    ) -> Self {
+        let edges: Vec<(usize, usize, usize)> =
+            tuples.iter().map(|&(u, v, w)| (u - 1, v - 1, w)).collect();
+        Graph::from_edges(n, &edges, directed)
+    }
+}
+
+impl<W: Weight> Graph<W> {
+    /// Graph from 0-indexed edges (from, to, weight)
+    pub fn from_edges(n: usize, edges: &[(usize, usize, W)], directed: bool) -> Self {
+        let arcs: Vec<(usize, usize, W)> = edges
+            .iter()
+            .flat_map(|&(u, v, w)| {
+                let reversed = if directed { None } else { Some((v, u, w)) };
+                std::iter::once((u, v, w)).chain(reversed)
+            })
+            .collect();
+        let mut start = vec![0; n + 1];
+        for &(u, _, _) in &arcs {
+            start[u + 1] += 1;
+        }
+        for v in 0..n {
+            start[v + 1] += start[v];
+        }
+        // counting sort by from, keeping the given order
+        let mut filled = start.clone();
+        let mut arc_of_slot = vec![0; arcs.len()];
+        for (i, &(u, _, _)) in arcs.iter().enumerate() {
+            arc_of_slot[filled[u]] = i;
+            filled[u] += 1;
+        }
+        let targets = arc_of_slot
+            .into_iter()
+            .map(|i| (arcs[i].1, arcs[i].2))
+            .collect();
+        Graph {
+            start,
+            targets,
+            directed,
+        }
+    }
+
+    /// Number of vertexes
+    pub fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// For algorithms which only make sense on undirected graphs
+    pub(crate) fn assert_undirected(&self) {
+        assert!(!self.directed, "graph must be undirected");
+    }
+
+    /// (to, weight) of edges from v
+    pub fn edges_from(&self, v: usize) -> &[(usize, W)] {
+        &self.targets[self.start[v]..self.start[v + 1]]
+    }
+
+    /// Targets of edges from v
+    pub fn adjacent(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges_from(v).iter().map(|&(to, _)| to)
+    }
+
+    /// Graph whose edges are all reversed
+    /// ```
+    /// use competitive_tools_rust::graph::Graph;
+    /// let graph = Graph::from_atcoder_weighted_tuples(3, &[(1, 2, 5), (3, 2, 7)], true);
+    /// let reversed = graph.rev_edge_direction();
+    /// assert_eq!(reversed.edges_from(1), &[(0, 5), (2, 7)]);
+    /// assert!(reversed.is_directed());
+    /// let undirected = Graph::from_atcoder_tuples(2, &[(1, 2)], false);
+    /// assert_eq!(undirected.rev_edge_direction(), undirected);
+    /// ```
+    pub fn rev_edge_direction(&self) -> Self {
+        if !self.directed {
+            return self.clone();
+        }
+        let edges: Vec<(usize, usize, W)> = (0..self.len())
+            .flat_map(|v| self.edges_from(v).iter().map(move |&(to, w)| (to, v, w)))
+            .collect();
+        Graph::from_edges(self.len(), &edges, true)
+    }
+
+    /// Adjacency list for algorithms on `Vec<Vec<usize>>`
+    pub fn to_adjacency_list(&self) -> Vec<Vec<usize>> {
+        (0..self.len())
+            .map(|v| self.adjacent(v).collect())
+            .collect()
+    }
+
+    /// Edge lists for algorithms on `Vec<Vec<Edge>>`
+    pub fn to_edges_list(&self) -> Vec<Vec<Edge>> {
+        (0..self.len())
+            .map(|v| {
+                self.edges_from(v)
+                    .iter()
+                    .map(|&(to, w)| Edge { to, cost: w.cost() })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Shortest distances from s (unit weights for unweighted graphs)
+    /// ```
+    /// use competitive_tools_rust::graph::Graph;
+    /// let graph = Graph::from_atcoder_tuples(4, &[(1, 2), (2, 3), (1, 3)], true);
+    /// assert_eq!(graph.dijkstra(0), vec![Some(0), Some(1), Some(1), None]);
+    /// ```
+    pub fn dijkstra(&self, s: usize) -> Vec<Option<usize>> {
+        shortest_distances(s, self.len(), |v| {
+            self.edges_from(v).iter().map(|&(to, w)| (to, w.cost()))
+        })
+    }
+
+    /// ```
+    /// use competitive_tools_rust::graph::Graph;
+    /// let graph = Graph::from_atcoder_tuples(4, &[(1, 2), (2, 1), (1, 3), (3, 1), (1, 4)], true);
+    /// assert_eq!(graph.strongly_connected_component(), vec![0, 0, 0, 1]);
+    /// ```
+    pub fn strongly_connected_component(&self) -> Vec<usize> {
+        strongly_connected_component(self.len(), |v| self.adjacent(v))
+    }
+}
+
+impl<W: Weight> Dag for Graph<W> {
+    /// ```
+    /// use competitive_tools_rust::graph::{Dag, Graph};
+    /// // 1 -> 2 -> 0
+    /// let graph = Graph::from_edges(3, &[(1, 2, ()), (2, 0, ())], true);
+    /// assert_eq!(graph.topological_sort(), vec![1, 2, 0]);
+    /// ```
+    fn topological_sort(&self) -> Vec<usize> {
+        topological_sort(self.len(), |v| self.adjacent(v))
+    }
+}
+
+impl<W: Weight> DepthFirstSearch for Graph<W> {
+    fn dfs<Pre, Post>(&self, start: usize, seen: &mut [bool], pre: Pre, post: Post)
+    where
+        Pre: FnMut(usize, Option<usize>),
+        Post: FnMut(usize, Option<usize>),
+    {
+        dfs(|v| self.adjacent(v), start, seen, pre, post);
+    }
+}
+
+/// Only for undirected graphs (panics on directed ones)
+impl<W: Weight> BipartiteGraph for Graph<W> {
+    fn bi_partition(&self) -> (Vec<usize>, Vec<usize>) {
+        self.assert_undirected();
+        bi_partition(self.len(), |v| self.adjacent(v))
+    }
+
+    fn checked_bi_partition(&self) -> Option<Bipartition> {
+        self.assert_undirected();
+        two_coloring(self.len(), |v| self.adjacent(v)).ok()
+    }
+
+    fn odd_cycle(&self) -> Option<Vec<usize>> {
+        self.assert_undirected();
+        two_coloring(self.len(), |v| self.adjacent(v)).err()
+    }
+
+    fn maximum_matching(&self) -> Vec<(usize, usize)> {
+        self.assert_undirected();
+        maximum_matching(self.len(), |v| self.adjacent(v))
+    }
+}
+
+/// Only for undirected graphs (panics on directed ones)
+impl<W: Weight> SmallGraph for Graph<W> {
+    fn chromatic_number(&self) -> usize {
+        self.assert_undirected();
+        chromatic_number(&adjacency_masks(self.len(), |v| self.adjacent(v)))
+    }
+
+    fn maximum_independent_set(&self) -> Vec<usize> {
+        self.assert_undirected();
+        maximum_independent_set(&adjacency_masks(self.len(), |v| self.adjacent(v)))
+    }
+
+    fn maximum_clique(&self) -> Vec<usize> {
+        self.assert_undirected();
+        maximum_clique(&adjacency_masks(self.len(), |v| self.adjacent(v)))
+    }
+}
+
+/// Only for undirected graphs (panics on directed ones)
+impl<W: Weight> Rerooting for Graph<W> {
+    fn rerooting<T, M, A>(&self, identity: T, merge: M, add_root: A) -> Vec<T>
+    where
+        T: Clone,
+        M: Fn(&T, &T) -> T,
+        A: Fn(&T, usize) -> T,
+    {
+        self.assert_undirected();
+        rerooting(self.len(), |v| self.adjacent(v), identity, merge, add_root)
+    }
+}
+
+impl<W: Weight> EulerianTrail for Graph<W> {
+    /// Eulerian trail along the edges of the graph,
+    /// by the directed or undirected algorithm depending on `is_directed`
+    /// ```
+    /// use competitive_tools_rust::graph::{EulerianTrail, Graph};
+    /// let triangle = Graph::from_atcoder_tuples(3, &[(1, 2), (2, 3), (3, 1)], false);
+    /// assert_eq!(triangle.eulerian_trail().unwrap().len(), 4);
+    /// let path = Graph::from_atcoder_tuples(3, &[(2, 1), (3, 2)], true);
+    /// assert_eq!(path.eulerian_trail(), Some(vec![2, 1, 0]));
+    /// ```
+    fn eulerian_trail(&self) -> Option<Vec<usize>> {
+        if self.directed {
+            let edges = directed_edges(self.len(), |v| self.adjacent(v));
+            directed_eulerian_trail(self.len(), &edges).map(|(vertexes, _)| vertexes)
+        } else {
+            self.undirected_eulerian_trail()
+        }
+    }
+
+    /// Panics on directed graphs
+    fn undirected_eulerian_trail(&self) -> Option<Vec<usize>> {
+        self.assert_undirected();
+        let edges = undirected_edges(self.len(), |v| self.adjacent(v));
+        undirected_eulerian_trail(self.len(), &edges).map(|(vertexes, _)| vertexes)
     }
 }
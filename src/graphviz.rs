@@ -0,0 +1,213 @@
+use crate::graph::Edge;
+use std::fmt::Write;
+
+const PALETTE: [&str; 8] = [
+    "lightblue",
+    "lightpink",
+    "palegreen",
+    "khaki",
+    "plum",
+    "lightsalmon",
+    "lightcyan",
+    "wheat",
+];
+
+/// What to emphasize when rendering a graph
+#[derive(Clone, Debug, Default)]
+pub struct Highlight {
+    /// Group id of each vertex such as SCC ids or bipartition sides,
+    /// drawn as fill colors and shown in labels
+    pub groups: Option<Vec<usize>>,
+    /// Vertexes of a walk such as a shortest path, drawn in red
+    pub path: Vec<usize>,
+}
+
+impl Highlight {
+    /// Highlight of bipartition sides like `BipartiteGraph::bi_partition`
+    pub fn from_sides(n: usize, left: &[usize]) -> Self {
+        let mut groups = vec![1; n];
+        for &v in left {
+            groups[v] = 0;
+        }
+        Highlight {
+            groups: Some(groups),
+            path: vec![],
+        }
+    }
+}
+
+/// Graphviz DOT text of `Vec<Vec<usize>>` or `Vec<Vec<Edge>>` for debugging small cases.
+/// Paste the output to `dot -Tpng` or an online viewer.
+/// For undirected graphs, each edge must appear in the lists of both ends and is drawn once.
+pub trait Graphviz {
+    /// (to, label) of edges from each vertex
+    fn labeled_edges_list(&self) -> Vec<Vec<(usize, Option<usize>)>>;
+
+    /// ```
+    /// use competitive_tools_rust::graphviz::Graphviz;
+    /// let graph: Vec<Vec<usize>> = vec![vec![1], vec![2], vec![]];
+    /// assert_eq!(
+    ///     graph.to_dot(true),
+    ///     "digraph {\n  0;\n  1;\n  2;\n  0 -> 1;\n  1 -> 2;\n}\n"
+    /// );
+    /// ```
+    fn to_dot(&self, directed: bool) -> String {
+        self.to_dot_with(directed, &Highlight::default())
+    }
+
+    /// ```
+    /// use competitive_tools_rust::graph::Edge;
+    /// use competitive_tools_rust::graphviz::{Graphviz, Highlight};
+    /// // 0 -3- 1 -4- 2
+    /// let graph: Vec<Vec<Edge>> = vec![
+    ///     vec![Edge { to: 1, cost: 3 }],
+    ///     vec![Edge { to: 0, cost: 3 }, Edge { to: 2, cost: 4 }],
+    ///     vec![Edge { to: 1, cost: 4 }],
+    /// ];
+    /// let highlight = Highlight { groups: Some(vec![0, 1, 0]), path: vec![0, 1] };
+    /// assert_eq!(
+    ///     graph.to_dot_with(false, &highlight),
+    ///     "graph {\n  \
+    ///     0 [label=\"0 (0)\", style=filled, fillcolor=lightblue, color=red, penwidth=2];\n  \
+    ///     1 [label=\"1 (1)\", style=filled, fillcolor=lightpink, color=red, penwidth=2];\n  \
+    ///     2 [label=\"2 (0)\", style=filled, fillcolor=lightblue];\n  \
+    ///     0 -- 1 [label=3, color=red, penwidth=2];\n  \
+    ///     1 -- 2 [label=4];\n}\n"
+    /// );
+    /// ```
+    fn to_dot_with(&self, directed: bool, highlight: &Highlight) -> String {
+        let labeled_edges_list = self.labeled_edges_list();
+        let n = labeled_edges_list.len();
+        let mut on_path = vec![false; n];
+        for &v in &highlight.path {
+            on_path[v] = true;
+        }
+        let mut path_edges: Vec<(usize, usize)> = highlight
+            .path
+            .windows(2)
+            .map(|w| {
+                if directed || w[0] < w[1] {
+                    (w[0], w[1])
+                } else {
+                    (w[1], w[0])
+                }
+            })
+            .collect();
+
+        let mut dot = String::new();
+        dot.push_str(if directed { "digraph {\n" } else { "graph {\n" });
+        for v in 0..n {
+            let mut attributes = vec![];
+            if let Some(groups) = &highlight.groups {
+                attributes.push(format!("label=\"{} ({})\"", v, groups[v]));
+                attributes.push("style=filled".to_string());
+                attributes.push(format!("fillcolor={}", PALETTE[groups[v] % PALETTE.len()]));
+            }
+            if on_path[v] {
+                attributes.push("color=red".to_string());
+                attributes.push("penwidth=2".to_string());
+            }
+            write_line(&mut dot, &v.to_string(), &attributes);
+        }
+        let arrow = if directed { "->" } else { "--" };
+        for (v, labeled_edges) in labeled_edges_list.into_iter().enumerate() {
+            let mut self_loops = 0;
+            for (to, label) in labeled_edges {
+                if !directed {
+                    if to < v {
+                        continue;
+                    }
+                    if to == v {
+                        // self loops appear twice in the list
+                        self_loops += 1;
+                        if self_loops % 2 == 0 {
+                            continue;
+                        }
+                    }
+                }
+                let mut attributes = vec![];
+                if let Some(label) = label {
+                    attributes.push(format!("label={}", label));
+                }
+                // each step of the path marks one of the parallel edges
+                if let Some(i) = path_edges.iter().position(|&e| e == (v, to)) {
+                    path_edges.swap_remove(i);
+                    attributes.push("color=red".to_string());
+                    attributes.push("penwidth=2".to_string());
+                }
+                write_line(&mut dot, &format!("{} {} {}", v, arrow, to), &attributes);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn write_line(dot: &mut String, statement: &str, attributes: &[String]) {
+    if attributes.is_empty() {
+        writeln!(dot, "  {};", statement).unwrap();
+    } else {
+        writeln!(dot, "  {} [{}];", statement, attributes.join(", ")).unwrap();
+    }
+}
+
+impl Graphviz for Vec<Vec<usize>> {
+    fn labeled_edges_list(&self) -> Vec<Vec<(usize, Option<usize>)>> {
+        self.iter()
+            .map(|edges| edges.iter().map(|&to| (to, None)).collect())
+            .collect()
+    }
+}
+
+impl Graphviz for Vec<Vec<Edge>> {
+    fn labeled_edges_list(&self) -> Vec<Vec<(usize, Option<usize>)>> {
+        self.iter()
+            .map(|edges| edges.iter().map(|e| (e.to, Some(e.cost))).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{AdjacencyList, BipartiteGraph};
+    use crate::graphviz::{Graphviz, Highlight};
+    use crate::tree::Tree;
+
+    #[test]
+    fn test_usable_with_tree() {
+        let tree: Vec<Vec<usize>> = vec![vec![1], vec![0]];
+        assert_eq!(tree.vertex_count(), 2);
+        assert_eq!(tree.to_dot(false), "graph {\n  0;\n  1;\n  0 -- 1;\n}\n");
+    }
+
+    #[test]
+    fn test_undirected_edges_are_drawn_once() {
+        // 0 = 1 with a self loop on 2
+        let graph: Vec<Vec<usize>> = vec![vec![1, 1], vec![0, 0], vec![2, 2]];
+        let dot = graph.to_dot(false);
+        assert_eq!(dot.matches("0 -- 1;").count(), 2);
+        assert_eq!(dot.matches("2 -- 2;").count(), 1);
+    }
+
+    #[test]
+    fn test_highlight_scc_and_sides() {
+        // 0 <=> 1 -> 2
+        let graph: Vec<Vec<usize>> = vec![vec![1], vec![0, 2], vec![]];
+        let scc = graph.strongly_connected_component();
+        let dot = graph.to_dot_with(
+            true,
+            &Highlight {
+                groups: Some(scc),
+                path: vec![0, 1, 2],
+            },
+        );
+        assert!(dot.contains("0 -> 1 [color=red, penwidth=2];"));
+        assert!(dot.contains("1 -> 0;"));
+        assert!(dot.contains("1 -> 2 [color=red, penwidth=2];"));
+
+        let tree: Vec<Vec<usize>> = vec![vec![1], vec![0, 2], vec![1]];
+        let (left, _) = tree.bi_partition();
+        let dot = tree.to_dot_with(false, &Highlight::from_sides(3, &left));
+        assert!(dot.contains("1 [label=\"1 (1)\", style=filled, fillcolor=lightpink];"));
+    }
+}
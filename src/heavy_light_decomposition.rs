@@ -0,0 +1,413 @@
+use std::ops::Range;
+
+/// Heavy-Light Decomposition of a tree given as `Vec<Vec<usize>>`
+///
+/// Every vertex gets an index in `0..n` so that
+/// * each subtree is a contiguous range
+/// * each path is split into O(log n) contiguous ranges
+///
+/// so path and subtree queries can be answered by range structures
+/// such as `segment_tree::SegmentTree`.
+///
+/// For edge-weighted trees, the weight of an edge is stored at the index of its deeper vertex.
+pub struct HeavyLightDecomposition {
+    pub parent: Vec<Option<usize>>,
+    pub depth: Vec<usize>,
+    /// order\[i\] is the vertex whose index is i
+    pub order: Vec<usize>,
+    head: Vec<usize>,
+    in_index: Vec<usize>,
+    out_index: Vec<usize>,
+}
+
+impl HeavyLightDecomposition {
+    /// ```
+    /// use competitive_tools_rust::heavy_light_decomposition::HeavyLightDecomposition;
+    /// // 0 - 1 - 2
+    /// //     |
+    /// //     3 - 4
+    /// let tree = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    /// let hld = HeavyLightDecomposition::new(&tree, 0);
+    /// assert_eq!(hld.parent, vec![None, Some(0), Some(1), Some(1), Some(3)]);
+    /// assert_eq!(hld.depth, vec![0, 1, 2, 2, 3]);
+    /// // heavy path 0 - 1 - 3 - 4 comes first
+    /// assert_eq!(hld.order, vec![0, 1, 3, 4, 2]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the tree is not connected.
+    pub fn new(tree: &[Vec<usize>], root: usize) -> Self {
+        let n = tree.len();
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut pre_order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            pre_order.push(v);
+            for &to in &tree[v] {
+                if Some(to) != parent[v] {
+                    parent[to] = Some(v);
+                    depth[to] = depth[v] + 1;
+                    stack.push(to);
+                }
+            }
+        }
+        assert_eq!(pre_order.len(), n, "tree must be connected");
+
+        let mut size = vec![1; n];
+        let mut heavy: Vec<Option<usize>> = vec![None; n];
+        for &v in pre_order.iter().rev() {
+            if let Some(p) = parent[v] {
+                size[p] += size[v];
+            }
+        }
+        for &v in &pre_order {
+            heavy[v] = tree[v]
+                .iter()
+                .filter(|&&to| Some(to) != parent[v])
+                .max_by_key(|&&to| size[to])
+                .copied();
+        }
+
+        let mut head = vec![root; n];
+        let mut in_index = vec![0; n];
+        let mut out_index = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            in_index[v] = order.len();
+            out_index[v] = order.len() + size[v];
+            order.push(v);
+            for &to in &tree[v] {
+                if Some(to) != parent[v] && Some(to) != heavy[v] {
+                    head[to] = to;
+                    stack.push(to);
+                }
+            }
+            // visit the heavy child first
+            if let Some(h) = heavy[v] {
+                head[h] = head[v];
+                stack.push(h);
+            }
+        }
+
+        HeavyLightDecomposition {
+            parent,
+            depth,
+            order,
+            head,
+            in_index,
+            out_index,
+        }
+    }
+
+    /// Index of vertex v
+    pub fn index(&self, v: usize) -> usize {
+        self.in_index[v]
+    }
+
+    /// Index of the edge between u and v, which must be adjacent
+    /// ```
+    /// use competitive_tools_rust::heavy_light_decomposition::HeavyLightDecomposition;
+    /// let tree = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    /// let hld = HeavyLightDecomposition::new(&tree, 0);
+    /// assert_eq!(hld.edge_index(1, 2), hld.index(2));
+    /// assert_eq!(hld.edge_index(3, 1), hld.index(3));
+    /// ```
+    pub fn edge_index(&self, u: usize, v: usize) -> usize {
+        if self.parent[v] == Some(u) {
+            self.in_index[v]
+        } else {
+            self.in_index[u]
+        }
+    }
+
+    /// ```
+    /// use competitive_tools_rust::heavy_light_decomposition::HeavyLightDecomposition;
+    /// let tree = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    /// let hld = HeavyLightDecomposition::new(&tree, 0);
+    /// assert_eq!(hld.lca(2, 4), 1);
+    /// assert_eq!(hld.lca(3, 4), 3);
+    /// assert_eq!(hld.lca(0, 2), 0);
+    /// ```
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] > self.depth[self.head[v]] {
+                u = self.parent[self.head[u]].unwrap();
+            } else {
+                v = self.parent[self.head[v]].unwrap();
+            }
+        }
+        if self.depth[u] < self.depth[v] { u } else { v }
+    }
+
+    pub fn distance(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// Range of indexes of vertexes in the subtree of v
+    /// ```
+    /// use competitive_tools_rust::heavy_light_decomposition::HeavyLightDecomposition;
+    /// let tree = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    /// let hld = HeavyLightDecomposition::new(&tree, 0);
+    /// let mut subtree: Vec<usize> = hld.order[hld.subtree_range(1)].to_vec();
+    /// subtree.sort();
+    /// assert_eq!(subtree, vec![1, 2, 3, 4]);
+    /// ```
+    pub fn subtree_range(&self, v: usize) -> Range<usize> {
+        self.in_index[v]..self.out_index[v]
+    }
+
+    /// Range of indexes of edges in the subtree of v
+    pub fn edge_subtree_range(&self, v: usize) -> Range<usize> {
+        self.in_index[v] + 1..self.out_index[v]
+    }
+
+    /// Ranges of indexes of vertexes on the path from u to v, in the order of the path.
+    /// The flag is true when the range is walked from its end to its start,
+    /// which matters only for non-commutative aggregation.
+    /// ```
+    /// use competitive_tools_rust::heavy_light_decomposition::HeavyLightDecomposition;
+    /// use competitive_tools_rust::segment_tree::SegmentTree;
+    /// // 0 - 1 - 2
+    /// //     |
+    /// //     3 - 4
+    /// let tree = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    /// let weights = vec![5, 3, 7, 9, 4];
+    /// let hld = HeavyLightDecomposition::new(&tree, 0);
+    /// let seg = SegmentTree::from_vec(hld.order.iter().map(|&v| weights[v]).collect(), usize::MAX);
+    /// let path_min = |u, v| {
+    ///     hld.path_ranges(u, v)
+    ///         .into_iter()
+    ///         .map(|(range, _)| seg.query(range))
+    ///         .min()
+    ///         .unwrap()
+    /// };
+    /// assert_eq!(path_min(2, 4), 3);
+    /// assert_eq!(path_min(3, 4), 4);
+    /// assert_eq!(path_min(2, 2), 7);
+    /// // path 2 -> 1 -> 3 -> 4, walked in order
+    /// let path: Vec<usize> = hld
+    ///     .path_ranges(2, 4)
+    ///     .into_iter()
+    ///     .flat_map(|(range, reversed)| {
+    ///         let mut vs = hld.order[range].to_vec();
+    ///         if reversed {
+    ///             vs.reverse();
+    ///         }
+    ///         vs
+    ///     })
+    ///     .collect();
+    /// assert_eq!(path, vec![2, 1, 3, 4]);
+    /// ```
+    pub fn path_ranges(&self, u: usize, v: usize) -> Vec<(Range<usize>, bool)> {
+        self.inner_path_ranges(u, v, false)
+    }
+
+    /// Ranges of indexes of edges on the path from u to v, in the order of the path.
+    /// The flag means the same as `path_ranges`.
+    /// ```
+    /// use competitive_tools_rust::heavy_light_decomposition::HeavyLightDecomposition;
+    /// use competitive_tools_rust::segment_tree::SegmentTree;
+    /// // 0 -5- 1 -3- 2
+    /// //       |
+    /// //       7
+    /// //       |
+    /// //       3 -2- 4
+    /// let edges = vec![(0, 1, 5), (1, 2, 3), (1, 3, 7), (3, 4, 2)];
+    /// let mut tree = vec![vec![]; 5];
+    /// for &(u, v, _) in &edges {
+    ///     tree[u].push(v);
+    ///     tree[v].push(u);
+    /// }
+    /// let hld = HeavyLightDecomposition::new(&tree, 0);
+    /// let mut seg = SegmentTree::new(5, usize::MAX);
+    /// for &(u, v, w) in &edges {
+    ///     seg.update(hld.edge_index(u, v), w);
+    /// }
+    /// let path_min = |u, v| {
+    ///     hld.edge_path_ranges(u, v)
+    ///         .into_iter()
+    ///         .map(|(range, _)| seg.query(range))
+    ///         .min()
+    ///         .unwrap_or(usize::MAX)
+    /// };
+    /// assert_eq!(path_min(0, 4), 2);
+    /// assert_eq!(path_min(0, 3), 5);
+    /// assert_eq!(path_min(2, 3), 3);
+    /// assert_eq!(path_min(3, 3), usize::MAX);
+    /// ```
+    pub fn edge_path_ranges(&self, u: usize, v: usize) -> Vec<(Range<usize>, bool)> {
+        self.inner_path_ranges(u, v, true)
+    }
+
+    fn inner_path_ranges(
+        &self,
+        mut u: usize,
+        mut v: usize,
+        edge: bool,
+    ) -> Vec<(Range<usize>, bool)> {
+        let mut from_u = vec![];
+        let mut from_v = vec![];
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] >= self.depth[self.head[v]] {
+                from_u.push((self.in_index[self.head[u]]..self.in_index[u] + 1, true));
+                u = self.parent[self.head[u]].unwrap();
+            } else {
+                from_v.push((self.in_index[self.head[v]]..self.in_index[v] + 1, false));
+                v = self.parent[self.head[v]].unwrap();
+            }
+        }
+        let skip = if edge { 1 } else { 0 };
+        if self.depth[u] >= self.depth[v] {
+            from_u.push((self.in_index[v] + skip..self.in_index[u] + 1, true));
+        } else {
+            from_v.push((self.in_index[u] + skip..self.in_index[v] + 1, false));
+        }
+        from_u
+            .into_iter()
+            .chain(from_v.into_iter().rev())
+            .filter(|(range, _)| !range.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::heavy_light_decomposition::HeavyLightDecomposition;
+    use crate::test_util::path_graph;
+
+    /// 0 - 1 - 2 - 3
+    ///     |   |
+    ///     4   5 - 6
+    ///     |
+    ///     7 - 8 - 9
+    fn sample_tree() -> Vec<Vec<usize>> {
+        let edges = vec![
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (1, 4),
+            (2, 5),
+            (5, 6),
+            (4, 7),
+            (7, 8),
+            (8, 9),
+        ];
+        let mut tree = vec![vec![]; 10];
+        for (u, v) in edges {
+            tree[u].push(v);
+            tree[v].push(u);
+        }
+        tree
+    }
+
+    fn naive_path(tree: &[Vec<usize>], u: usize, v: usize) -> Vec<usize> {
+        let mut prev = vec![None; tree.len()];
+        let mut stack = vec![u];
+        prev[u] = Some(u);
+        while let Some(x) = stack.pop() {
+            for &to in &tree[x] {
+                if prev[to].is_none() {
+                    prev[to] = Some(x);
+                    stack.push(to);
+                }
+            }
+        }
+        let mut path = vec![v];
+        let mut x = v;
+        while x != u {
+            x = prev[x].unwrap();
+            path.push(x);
+        }
+        path.reverse();
+        path
+    }
+
+    fn walk(
+        hld: &HeavyLightDecomposition,
+        ranges: Vec<(std::ops::Range<usize>, bool)>,
+    ) -> Vec<usize> {
+        ranges
+            .into_iter()
+            .flat_map(|(range, reversed)| {
+                let mut vs = hld.order[range].to_vec();
+                if reversed {
+                    vs.reverse();
+                }
+                vs
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_path_ranges_keep_direction() {
+        let tree = sample_tree();
+        for root in 0..tree.len() {
+            let hld = HeavyLightDecomposition::new(&tree, root);
+            for u in 0..tree.len() {
+                for v in 0..tree.len() {
+                    let path = naive_path(&tree, u, v);
+                    assert_eq!(walk(&hld, hld.path_ranges(u, v)), path);
+                    let lca = hld.lca(u, v);
+                    let edge_path: Vec<usize> = path.into_iter().filter(|&x| x != lca).collect();
+                    let mut edges = walk(&hld, hld.edge_path_ranges(u, v));
+                    let mut expected = edge_path;
+                    edges.sort();
+                    expected.sort();
+                    assert_eq!(edges, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_commutative_path_aggregation() {
+        let tree = sample_tree();
+        let labels: Vec<char> = "abcdefghij".chars().collect();
+        let hld = HeavyLightDecomposition::new(&tree, 3);
+        let concat = |u, v| -> String {
+            hld.path_ranges(u, v)
+                .into_iter()
+                .map(|(range, reversed)| {
+                    let s: String = hld.order[range].iter().map(|&x| labels[x]).collect();
+                    if reversed {
+                        s.chars().rev().collect()
+                    } else {
+                        s
+                    }
+                })
+                .collect()
+        };
+        assert_eq!(concat(9, 6), "jihebcfg");
+        assert_eq!(concat(6, 9), "gfcbehij");
+        assert_eq!(concat(0, 3), "abcd");
+    }
+
+    #[test]
+    fn test_subtree_range() {
+        let tree = sample_tree();
+        let hld = HeavyLightDecomposition::new(&tree, 0);
+        let mut subtree = hld.order[hld.subtree_range(4)].to_vec();
+        subtree.sort();
+        assert_eq!(subtree, vec![4, 7, 8, 9]);
+        assert_eq!(hld.edge_subtree_range(4).len(), 3);
+        assert_eq!(hld.distance(9, 6), 7);
+    }
+
+    #[test]
+    fn test_deep_path_graph() {
+        let n = 200_000;
+        let tree: Vec<Vec<usize>> = path_graph(n);
+        let hld = HeavyLightDecomposition::new(&tree, 0);
+        assert_eq!(hld.path_ranges(0, n - 1), vec![(0..n, false)]);
+        assert_eq!(hld.lca(n - 1, n / 2), n / 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "tree must be connected")]
+    fn test_forest() {
+        let forest = vec![vec![1], vec![0], vec![3], vec![2]];
+        HeavyLightDecomposition::new(&forest, 0);
+    }
+}
@@ -1,13 +1,29 @@
+pub mod arborescence;
+pub mod bipartite_matching;
 #[macro_use]
 pub mod bitset;
+pub mod centroid_decomposition;
 pub mod clonetools;
 pub mod coordinate_compression;
+pub mod dominator_tree;
+pub mod euler_tour;
+pub mod functional_graph;
 pub mod graph;
+pub mod graphviz;
+pub mod heavy_light_decomposition;
 pub mod io;
+pub mod link_cut_tree;
+pub mod lowlink;
 pub mod math;
+pub mod max_flow;
 pub mod maze;
+pub mod min_cost_flow;
 pub mod my_itertools;
 pub mod search;
 pub mod segment_tree;
+#[cfg(test)]
+mod test_util;
+pub mod tree;
+pub mod tsp;
 pub mod union_find;
 pub mod vectools;
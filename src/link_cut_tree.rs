@@ -0,0 +1,376 @@
+#[derive(Clone, Copy, Debug)]
+struct Node<T> {
+    left: Option<usize>,
+    right: Option<usize>,
+    /// Parent in the splay tree, or path-parent for the root of a splay tree
+    parent: Option<usize>,
+    value: T,
+    /// Fold of the splay subtree from left to right
+    sum: T,
+    /// Fold of the splay subtree from right to left
+    rev_sum: T,
+    /// The children must still be reversed (this node itself already is)
+    reversed: bool,
+}
+
+/// Link-cut tree for a dynamic forest with path folds over a monoid,
+/// where op is associative (not necessarily commutative) and identity is its identity element.
+/// Every operation is O(log n) amortized.
+pub struct LinkCutTree<T> {
+    nodes: Vec<Node<T>>,
+    identity: T,
+    op: fn(T, T) -> T,
+}
+
+impl<T: Copy> LinkCutTree<T> {
+    /// Forest of isolated vertexes with the given values
+    pub fn new(values: Vec<T>, identity: T, op: fn(T, T) -> T) -> Self {
+        let nodes = values
+            .into_iter()
+            .map(|value| Node {
+                left: None,
+                right: None,
+                parent: None,
+                value,
+                sum: value,
+                rev_sum: value,
+                reversed: false,
+            })
+            .collect();
+        LinkCutTree {
+            nodes,
+            identity,
+            op,
+        }
+    }
+
+    fn is_splay_root(&self, x: usize) -> bool {
+        match self.nodes[x].parent {
+            None => true,
+            Some(p) => self.nodes[p].left != Some(x) && self.nodes[p].right != Some(x),
+        }
+    }
+
+    fn toggle(&mut self, x: usize) {
+        let node = &mut self.nodes[x];
+        std::mem::swap(&mut node.left, &mut node.right);
+        std::mem::swap(&mut node.sum, &mut node.rev_sum);
+        node.reversed ^= true;
+    }
+
+    fn push(&mut self, x: usize) {
+        if self.nodes[x].reversed {
+            self.nodes[x].reversed = false;
+            for child in [self.nodes[x].left, self.nodes[x].right]
+                .into_iter()
+                .flatten()
+            {
+                self.toggle(child);
+            }
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let op = self.op;
+        let value = self.nodes[x].value;
+        let (left_sum, left_rev_sum) = self.nodes[x]
+            .left
+            .map_or((self.identity, self.identity), |l| {
+                (self.nodes[l].sum, self.nodes[l].rev_sum)
+            });
+        let (right_sum, right_rev_sum) = self.nodes[x]
+            .right
+            .map_or((self.identity, self.identity), |r| {
+                (self.nodes[r].sum, self.nodes[r].rev_sum)
+            });
+        self.nodes[x].sum = op(op(left_sum, value), right_sum);
+        self.nodes[x].rev_sum = op(op(right_rev_sum, value), left_rev_sum);
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.nodes[x].parent.unwrap();
+        let grand = self.nodes[p].parent;
+        let p_is_root = self.is_splay_root(p);
+        if self.nodes[p].left == Some(x) {
+            let moved = self.nodes[x].right;
+            self.nodes[p].left = moved;
+            if let Some(m) = moved {
+                self.nodes[m].parent = Some(p);
+            }
+            self.nodes[x].right = Some(p);
+        } else {
+            let moved = self.nodes[x].left;
+            self.nodes[p].right = moved;
+            if let Some(m) = moved {
+                self.nodes[m].parent = Some(p);
+            }
+            self.nodes[x].left = Some(p);
+        }
+        self.nodes[p].parent = Some(x);
+        self.nodes[x].parent = grand;
+        if let Some(g) = grand
+            && !p_is_root
+        {
+            if self.nodes[g].left == Some(p) {
+                self.nodes[g].left = Some(x);
+            } else {
+                self.nodes[g].right = Some(x);
+            }
+        }
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        // push the reversals from the root of the splay tree down to x
+        let mut path = vec![x];
+        let mut y = x;
+        while !self.is_splay_root(y) {
+            y = self.nodes[y].parent.unwrap();
+            path.push(y);
+        }
+        for &y in path.iter().rev() {
+            self.push(y);
+        }
+        while !self.is_splay_root(x) {
+            let p = self.nodes[x].parent.unwrap();
+            if !self.is_splay_root(p) {
+                let g = self.nodes[p].parent.unwrap();
+                let zig_zig = (self.nodes[g].left == Some(p)) == (self.nodes[p].left == Some(x));
+                self.rotate(if zig_zig { p } else { x });
+            }
+            self.rotate(x);
+        }
+    }
+
+    /// Make the path from the root to x preferred, with x at the root of its splay tree.
+    /// Returns the last vertex where the path joined the previously exposed one.
+    fn expose(&mut self, x: usize) -> usize {
+        let mut last = None;
+        let mut joined = x;
+        let mut y = Some(x);
+        while let Some(c) = y {
+            self.splay(c);
+            self.nodes[c].right = last;
+            self.update(c);
+            last = Some(c);
+            joined = c;
+            y = self.nodes[c].parent;
+        }
+        self.splay(x);
+        joined
+    }
+
+    /// Make v the root of its tree
+    pub fn evert(&mut self, v: usize) {
+        self.expose(v);
+        self.toggle(v);
+    }
+
+    /// ```
+    /// use competitive_tools_rust::link_cut_tree::LinkCutTree;
+    /// let mut lct = LinkCutTree::new(vec![0; 3], 0, |a, b| a + b);
+    /// lct.link(0, 1);
+    /// assert!(lct.connected(0, 1));
+    /// assert!(!lct.connected(0, 2));
+    /// ```
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        if u == v {
+            return true;
+        }
+        self.expose(u);
+        self.expose(v);
+        self.nodes[u].parent.is_some()
+    }
+
+    /// Add edge u - v, where u and v must be in different trees.
+    /// The tree of u is hung below v.
+    pub fn link(&mut self, u: usize, v: usize) {
+        assert!(!self.connected(u, v), "u and v are already connected");
+        self.evert(u);
+        self.nodes[u].parent = Some(v);
+    }
+
+    /// Remove edge u - v, which must exist
+    /// ```
+    /// use competitive_tools_rust::link_cut_tree::LinkCutTree;
+    /// // 0 - 1 - 2
+    /// let mut lct = LinkCutTree::new(vec![0; 3], 0, |a, b| a + b);
+    /// lct.link(0, 1);
+    /// lct.link(1, 2);
+    /// lct.cut(1, 0);
+    /// assert!(!lct.connected(0, 2));
+    /// assert!(lct.connected(1, 2));
+    /// ```
+    pub fn cut(&mut self, u: usize, v: usize) {
+        self.evert(u);
+        self.expose(v);
+        // now the path is u - v, so u is the only node left of v
+        self.push(v);
+        let left = self.nodes[v].left;
+        assert_eq!(left, Some(u), "no edge between u and v");
+        self.push(u);
+        assert!(self.nodes[u].right.is_none(), "no edge between u and v");
+        self.nodes[v].left = None;
+        self.nodes[u].parent = None;
+        self.update(v);
+    }
+
+    /// Lowest common ancestor of u and v when the tree is rooted at root, None if not connected
+    /// ```
+    /// use competitive_tools_rust::link_cut_tree::LinkCutTree;
+    /// // 0 - 1 - 2
+    /// //     |
+    /// //     3   4
+    /// let mut lct = LinkCutTree::new(vec![0; 5], 0, |a, b| a + b);
+    /// lct.link(1, 0);
+    /// lct.link(2, 1);
+    /// lct.link(3, 1);
+    /// assert_eq!(lct.lca(0, 2, 3), Some(1));
+    /// assert_eq!(lct.lca(0, 2, 0), Some(0));
+    /// assert_eq!(lct.lca(0, 2, 4), None);
+    /// assert_eq!(lct.lca(2, 0, 3), Some(1));
+    /// ```
+    pub fn lca(&mut self, root: usize, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(root, u) || !self.connected(root, v) {
+            return None;
+        }
+        self.evert(root);
+        self.expose(u);
+        Some(self.expose(v))
+    }
+
+    /// Fold of the values on the path from u to v in this order, None if not connected
+    /// ```
+    /// use competitive_tools_rust::link_cut_tree::LinkCutTree;
+    /// // 0 - 1 - 2 - 3 with digits as values, folded as decimal numbers
+    /// let op = |a: (usize, usize), b: (usize, usize)| (a.0 * b.1 + b.0, a.1 * b.1);
+    /// let values = (0..4).map(|d| (d + 1, 10)).collect();
+    /// let mut lct = LinkCutTree::new(values, (0, 1), op);
+    /// lct.link(0, 1);
+    /// lct.link(1, 2);
+    /// lct.link(2, 3);
+    /// assert_eq!(lct.fold(0, 3), Some((1234, 10000)));
+    /// assert_eq!(lct.fold(2, 0), Some((321, 1000)));
+    /// lct.set(1, (9, 10));
+    /// assert_eq!(lct.fold(3, 1), Some((439, 1000)));
+    /// assert_eq!(lct.get(1), (9, 10));
+    /// ```
+    pub fn fold(&mut self, u: usize, v: usize) -> Option<T> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.evert(u);
+        self.expose(v);
+        Some(self.nodes[v].sum)
+    }
+
+    pub fn set(&mut self, v: usize, value: T) {
+        self.expose(v);
+        self.nodes[v].value = value;
+        self.update(v);
+    }
+
+    pub fn get(&self, v: usize) -> T {
+        self.nodes[v].value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::link_cut_tree::LinkCutTree;
+    use crate::test_util::Rng;
+
+    const MOD: usize = 1_000_000_007;
+
+    /// Polynomial hash of a sequence, which is not commutative
+    fn op(a: (usize, usize), b: (usize, usize)) -> (usize, usize) {
+        ((a.0 * b.1 + b.0) % MOD, a.1 * b.1 % MOD)
+    }
+
+    fn path(adjacency: &[Vec<usize>], u: usize, v: usize) -> Option<Vec<usize>> {
+        let mut prev = vec![None; adjacency.len()];
+        let mut seen = vec![false; adjacency.len()];
+        seen[u] = true;
+        let mut stack = vec![u];
+        while let Some(x) = stack.pop() {
+            for &to in &adjacency[x] {
+                if !seen[to] {
+                    seen[to] = true;
+                    prev[to] = Some(x);
+                    stack.push(to);
+                }
+            }
+        }
+        if !seen[v] {
+            return None;
+        }
+        let mut path = vec![v];
+        let mut x = v;
+        while let Some(p) = prev[x] {
+            path.push(p);
+            x = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    #[test]
+    fn test_with_naive_forest() {
+        let mut rng = Rng::new(67);
+        for _ in 0..30 {
+            let n = 1 + rng.rand(12);
+            let mut values: Vec<(usize, usize)> = (0..n).map(|_| (rng.rand(100), 131)).collect();
+            let mut lct = LinkCutTree::new(values.clone(), (0, 1), op);
+            let mut adjacency: Vec<Vec<usize>> = vec![vec![]; n];
+            for _ in 0..300 {
+                let (u, v) = (rng.rand(n), rng.rand(n));
+                let connected = path(&adjacency, u, v).is_some();
+                assert_eq!(lct.connected(u, v), connected);
+                match rng.rand(5) {
+                    0 if !connected => {
+                        lct.link(u, v);
+                        adjacency[u].push(v);
+                        adjacency[v].push(u);
+                    }
+                    1 if adjacency[u].contains(&v) => {
+                        lct.cut(u, v);
+                        adjacency[u].retain(|&x| x != v);
+                        adjacency[v].retain(|&x| x != u);
+                    }
+                    2 => {
+                        let value = (rng.rand(100), 131);
+                        lct.set(u, value);
+                        values[u] = value;
+                    }
+                    3 => {
+                        let w = rng.rand(n);
+                        let expected = match (path(&adjacency, v, u), path(&adjacency, w, u)) {
+                            (Some(a), Some(b)) => a.into_iter().find(|x| b.contains(x)),
+                            _ => None,
+                        };
+                        assert_eq!(lct.lca(u, v, w), expected);
+                    }
+                    _ => {
+                        let expected = path(&adjacency, u, v)
+                            .map(|p| p.iter().fold((0, 1), |acc, &x| op(acc, values[x])));
+                        assert_eq!(lct.fold(u, v), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_path() {
+        let n = 100_000;
+        let mut lct = LinkCutTree::new(vec![1; n], 0, |a, b| a + b);
+        for v in 1..n {
+            lct.link(v, v - 1);
+        }
+        assert_eq!(lct.fold(0, n - 1), Some(n));
+        lct.cut(n / 2, n / 2 - 1);
+        assert_eq!(lct.fold(n - 1, n / 2), Some(n - n / 2));
+        assert!(!lct.connected(0, n - 1));
+    }
+}
@@ -0,0 +1,310 @@
+/// Lowlink of an undirected graph given as `Vec<Vec<usize>>`,
+/// where each edge appears in the lists of both ends.
+/// Multi-edges are allowed (parallel edges are never bridges) and self loops are ignored.
+pub struct LowLink {
+    /// Pre-order of DFS
+    pub ord: Vec<usize>,
+    /// Minimum ord reachable by tree edges downward and at most one back edge
+    pub low: Vec<usize>,
+    /// Parent in the DFS tree
+    pub parent: Vec<Option<usize>>,
+    /// Bridges as (smaller vertex, larger vertex) in ascending order
+    pub bridges: Vec<(usize, usize)>,
+    /// Articulation points in ascending order
+    pub articulation_points: Vec<usize>,
+    /// Vertexes of each biconnected component (block) in ascending order.
+    /// Articulation points belong to several blocks, and isolated vertexes form their own blocks.
+    pub biconnected_components: Vec<Vec<usize>>,
+    /// Whether the edge between v and parent\[v\] is a bridge
+    is_bridge_to_parent: Vec<bool>,
+}
+
+impl LowLink {
+    /// ```
+    /// use competitive_tools_rust::lowlink::LowLink;
+    /// // 0 - 1 - 2 = 3
+    /// //  \ /
+    /// //   4
+    /// let graph = vec![vec![1, 4], vec![0, 2, 4], vec![1, 3, 3], vec![2, 2], vec![0, 1]];
+    /// let lowlink = LowLink::new(&graph);
+    /// assert_eq!(lowlink.bridges, vec![(1, 2)]);
+    /// assert_eq!(lowlink.articulation_points, vec![1, 2]);
+    /// assert_eq!(lowlink.biconnected_components, vec![vec![2, 3], vec![1, 2], vec![0, 1, 4]]);
+    /// ```
+    pub fn new(graph: &[Vec<usize>]) -> Self {
+        let n = graph.len();
+        let mut ord = vec![usize::MAX; n];
+        let mut low = vec![usize::MAX; n];
+        let mut parent: Vec<Option<usize>> = vec![None; n];
+        let mut parent_edge_skipped = vec![false; n];
+        let mut is_bridge_to_parent = vec![false; n];
+        let mut is_articulation = vec![false; n];
+        let mut biconnected_components = vec![];
+        let mut edge_stack: Vec<(usize, usize)> = vec![];
+        let mut counter = 0;
+        for root in 0..n {
+            if ord[root] != usize::MAX {
+                continue;
+            }
+            ord[root] = counter;
+            low[root] = counter;
+            counter += 1;
+            let mut root_children = 0;
+            // (vertex, index of the next edge)
+            let mut stack = vec![(root, 0)];
+            while let Some((v, i)) = stack.pop() {
+                if let Some(&to) = graph[v].get(i) {
+                    stack.push((v, i + 1));
+                    if to == v {
+                        continue;
+                    }
+                    if Some(to) == parent[v] && !parent_edge_skipped[v] {
+                        // the tree edge itself, but parallel edges are back edges
+                        parent_edge_skipped[v] = true;
+                        continue;
+                    }
+                    if ord[to] == usize::MAX {
+                        parent[to] = Some(v);
+                        ord[to] = counter;
+                        low[to] = counter;
+                        counter += 1;
+                        edge_stack.push((v, to));
+                        stack.push((to, 0));
+                    } else if ord[to] < ord[v] {
+                        low[v] = low[v].min(ord[to]);
+                        edge_stack.push((v, to));
+                    }
+                } else if let Some(p) = parent[v] {
+                    low[p] = low[p].min(low[v]);
+                    if low[v] > ord[p] {
+                        is_bridge_to_parent[v] = true;
+                    }
+                    if p == root {
+                        root_children += 1;
+                    }
+                    if low[v] >= ord[p] {
+                        if p != root {
+                            is_articulation[p] = true;
+                        }
+                        let mut block = vec![];
+                        while let Some((a, b)) = edge_stack.pop() {
+                            block.push(a);
+                            block.push(b);
+                            if (a, b) == (p, v) {
+                                break;
+                            }
+                        }
+                        block.sort_unstable();
+                        block.dedup();
+                        biconnected_components.push(block);
+                    }
+                }
+            }
+            if root_children >= 2 {
+                is_articulation[root] = true;
+            }
+            if root_children == 0 {
+                biconnected_components.push(vec![root]);
+            }
+        }
+        let mut bridges: Vec<(usize, usize)> = (0..n)
+            .filter(|&v| is_bridge_to_parent[v])
+            .map(|v| {
+                let p = parent[v].unwrap();
+                (p.min(v), p.max(v))
+            })
+            .collect();
+        bridges.sort_unstable();
+        LowLink {
+            ord,
+            low,
+            parent,
+            bridges,
+            articulation_points: (0..n).filter(|&v| is_articulation[v]).collect(),
+            biconnected_components,
+            is_bridge_to_parent,
+        }
+    }
+
+    fn is_bridge(&self, u: usize, v: usize) -> bool {
+        (self.parent[v] == Some(u) && self.is_bridge_to_parent[v])
+            || (self.parent[u] == Some(v) && self.is_bridge_to_parent[u])
+    }
+
+    /// Two-edge-connected component id of each vertex, and the bridge tree
+    /// whose vertexes are the components and whose edges are the bridges
+    /// ```
+    /// use competitive_tools_rust::lowlink::LowLink;
+    /// // 0 - 1 - 2 = 3
+    /// //  \ /
+    /// //   4
+    /// let graph = vec![vec![1, 4], vec![0, 2, 4], vec![1, 3, 3], vec![2, 2], vec![0, 1]];
+    /// let lowlink = LowLink::new(&graph);
+    /// let (component, bridge_tree) = lowlink.two_edge_connected_components(&graph);
+    /// assert_eq!(component, vec![0, 0, 1, 1, 0]);
+    /// assert_eq!(bridge_tree, vec![vec![1], vec![0]]);
+    /// ```
+    pub fn two_edge_connected_components(
+        &self,
+        graph: &[Vec<usize>],
+    ) -> (Vec<usize>, Vec<Vec<usize>>) {
+        let n = graph.len();
+        let mut component = vec![usize::MAX; n];
+        let mut count = 0;
+        for start in 0..n {
+            if component[start] != usize::MAX {
+                continue;
+            }
+            component[start] = count;
+            let mut stack = vec![start];
+            while let Some(v) = stack.pop() {
+                for &to in &graph[v] {
+                    if component[to] == usize::MAX && !self.is_bridge(v, to) {
+                        component[to] = count;
+                        stack.push(to);
+                    }
+                }
+            }
+            count += 1;
+        }
+        let mut bridge_tree = vec![vec![]; count];
+        for &(u, v) in &self.bridges {
+            bridge_tree[component[u]].push(component[v]);
+            bridge_tree[component[v]].push(component[u]);
+        }
+        (component, bridge_tree)
+    }
+
+    /// Block-cut tree with `n + biconnected_components.len()` vertexes:
+    /// vertex v < n is the original vertex, and vertex n + b is the block b.
+    /// Each original vertex is connected to the blocks containing it,
+    /// so articulation points are exactly the original vertexes of degree 2 or more.
+    /// ```
+    /// use competitive_tools_rust::lowlink::LowLink;
+    /// // 0 - 1 - 2
+    /// let graph = vec![vec![1], vec![0, 2], vec![1]];
+    /// let lowlink = LowLink::new(&graph);
+    /// assert_eq!(lowlink.biconnected_components, vec![vec![1, 2], vec![0, 1]]);
+    /// assert_eq!(lowlink.block_cut_tree(), vec![
+    ///     vec![4],
+    ///     vec![3, 4],
+    ///     vec![3],
+    ///     vec![1, 2],
+    ///     vec![0, 1],
+    /// ]);
+    /// ```
+    pub fn block_cut_tree(&self) -> Vec<Vec<usize>> {
+        let n = self.ord.len();
+        let mut tree = vec![vec![]; n + self.biconnected_components.len()];
+        for (b, block) in self.biconnected_components.iter().enumerate() {
+            for &v in block {
+                tree[v].push(n + b);
+                tree[n + b].push(v);
+            }
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lowlink::LowLink;
+    use crate::test_util::{Rng, path_graph};
+
+    fn count_components(
+        n: usize,
+        edges: &[(usize, usize)],
+        removed_vertex: Option<usize>,
+    ) -> usize {
+        let mut seen = vec![false; n];
+        let mut count = 0;
+        for start in 0..n {
+            if seen[start] || Some(start) == removed_vertex {
+                continue;
+            }
+            count += 1;
+            seen[start] = true;
+            let mut stack = vec![start];
+            while let Some(v) = stack.pop() {
+                for &(a, b) in edges {
+                    for (x, y) in [(a, b), (b, a)] {
+                        if x == v && !seen[y] && Some(y) != removed_vertex {
+                            seen[y] = true;
+                            stack.push(y);
+                        }
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_with_naive_removal() {
+        let mut rng = Rng::new(23);
+        for _ in 0..300 {
+            let n = 1 + rng.rand(8);
+            let edges: Vec<(usize, usize)> = (0..rng.rand(12))
+                .map(|_| (rng.rand(n), rng.rand(n)))
+                .collect();
+            let mut graph = vec![vec![]; n];
+            for &(u, v) in &edges {
+                graph[u].push(v);
+                graph[v].push(u);
+            }
+            let lowlink = LowLink::new(&graph);
+            let base = count_components(n, &edges, None);
+
+            let mut bridges = vec![];
+            for i in 0..edges.len() {
+                let rest: Vec<(usize, usize)> = edges
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &e)| e)
+                    .collect();
+                if count_components(n, &rest, None) > base {
+                    let (u, v) = edges[i];
+                    bridges.push((u.min(v), u.max(v)));
+                }
+            }
+            bridges.sort();
+            assert_eq!(lowlink.bridges, bridges);
+
+            let articulation_points: Vec<usize> = (0..n)
+                .filter(|&v| {
+                    let isolated = edges.iter().all(|&(a, b)| a != v && b != v || a == b);
+                    !isolated && count_components(n, &edges, Some(v)) > base
+                })
+                .collect();
+            assert_eq!(lowlink.articulation_points, articulation_points);
+
+            // every non-loop edge belongs to exactly one block
+            for &(u, v) in edges.iter().filter(|(u, v)| u != v) {
+                let count = lowlink
+                    .biconnected_components
+                    .iter()
+                    .filter(|block| block.contains(&u) && block.contains(&v))
+                    .count();
+                assert_eq!(count, 1);
+            }
+            let (component, bridge_tree) = lowlink.two_edge_connected_components(&graph);
+            for &(u, v) in &edges {
+                let is_bridge = lowlink.bridges.contains(&(u.min(v), u.max(v)));
+                assert_eq!(component[u] != component[v], is_bridge);
+            }
+            let tree_edges: usize = bridge_tree.iter().map(|adj| adj.len()).sum();
+            assert_eq!(tree_edges, 2 * lowlink.bridges.len());
+        }
+    }
+
+    #[test]
+    fn test_deep_path_graph() {
+        let n = 200_000;
+        let graph: Vec<Vec<usize>> = path_graph(n);
+        let lowlink = LowLink::new(&graph);
+        assert_eq!(lowlink.bridges.len(), n - 1);
+        assert_eq!(lowlink.articulation_points.len(), n - 2);
+        assert_eq!(lowlink.biconnected_components.len(), n - 1);
+    }
+}
@@ -60,12 +60,12 @@ pub fn smallest_prime_factor(n: usize) -> Option<usize> {
     if n < 2 {
         return None;
     }
-    if n % 2 == 0 {
+    if n.is_multiple_of(2) {
         return Some(2);
     }
     let max_sqrt = (n as f64).sqrt().ceil() as usize;
     for odd in (3..=max_sqrt).step_by(2) {
-        if n % odd == 0 {
+        if n.is_multiple_of(odd) {
             return Some(odd);
         }
     }
@@ -242,6 +242,40 @@ impl ModPow for usize {
     }
 }
 
+pub trait Divisors
+where
+    Self: Sized,
+{
+    fn divisors(&self) -> Vec<Self>;
+}
+
+impl Divisors for usize {
+    /// ```
+    /// use competitive_tools_rust::math::Divisors;
+    /// assert_eq!(1.divisors(), vec![1]);
+    /// assert_eq!(2.divisors(), vec![1, 2]);
+    /// assert_eq!(3.divisors(), vec![1, 3]);
+    /// assert_eq!(4.divisors(), vec![1, 2, 4]);
+    /// assert_eq!(24.divisors(), vec![1, 2, 3, 4, 6, 8, 12, 24]);
+    /// ```
+    fn divisors(&self) -> Vec<Self> {
+        let mut dvs = vec![];
+        for i in 1..=*self {
+            if i * i > *self {
+                break;
+            }
+            if self.is_multiple_of(i) {
+                dvs.push(i);
+                if i * i != *self {
+                    dvs.push(*self / i)
+                }
+            }
+        }
+        dvs.sort_unstable();
+        dvs
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::math::ModPow;
@@ -288,37 +322,3 @@ mod tests {
         }
     }
 }
-
-pub trait Divisors
-where
-    Self: Sized,
-{
-    fn divisors(&self) -> Vec<Self>;
-}
-
-impl Divisors for usize {
-    /// ```
-    /// use competitive_tools_rust::math::Divisors;
-    /// assert_eq!(1.divisors(), vec![1]);
-    /// assert_eq!(2.divisors(), vec![1, 2]);
-    /// assert_eq!(3.divisors(), vec![1, 3]);
-    /// assert_eq!(4.divisors(), vec![1, 2, 4]);
-    /// assert_eq!(24.divisors(), vec![1, 2, 3, 4, 6, 8, 12, 24]);
-    /// ```
-    fn divisors(&self) -> Vec<Self> {
-        let mut dvs = vec![];
-        for i in 1..=*self {
-            if i * i > *self {
-                break;
-            }
-            if *self % i == 0 {
-                dvs.push(i);
-                if i * i != *self {
-                    dvs.push(*self / i)
-                }
-            }
-        }
-        dvs.sort_unstable();
-        dvs
-    }
-}
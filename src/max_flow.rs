@@ -0,0 +1,339 @@
+use std::collections::VecDeque;
+
+/// Edge of MaxFlow after (or before) running flow
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FlowEdge {
+    pub from: usize,
+    pub to: usize,
+    pub cap: usize,
+    pub flow: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ResidualEdge {
+    to: usize,
+    /// index of the reverse edge in graph\[to\]
+    rev: usize,
+    cap: usize,
+}
+
+/// Maximum flow by Dinic's algorithm
+/// Vertexes are 0-indexed like `graph`
+pub struct MaxFlow {
+    graph: Vec<Vec<ResidualEdge>>,
+    /// (from, index in graph\[from\]) of each added edge
+    pos: Vec<(usize, usize)>,
+}
+
+impl MaxFlow {
+    pub fn new(n: usize) -> Self {
+        MaxFlow {
+            graph: vec![vec![]; n],
+            pos: vec![],
+        }
+    }
+
+    /// Add directed edge and return its id
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: usize) -> usize {
+        let id = self.pos.len();
+        let from_index = self.graph[from].len();
+        let to_index = self.graph[to].len() + if from == to { 1 } else { 0 };
+        self.pos.push((from, from_index));
+        self.graph[from].push(ResidualEdge {
+            to,
+            rev: to_index,
+            cap,
+        });
+        self.graph[to].push(ResidualEdge {
+            to: from,
+            rev: from_index,
+            cap: 0,
+        });
+        id
+    }
+
+    /// ```
+    /// use competitive_tools_rust::max_flow::{FlowEdge, MaxFlow};
+    /// let mut g = MaxFlow::new(2);
+    /// let id = g.add_edge(0, 1, 5);
+    /// g.flow(0, 1);
+    /// assert_eq!(g.get_edge(id), FlowEdge { from: 0, to: 1, cap: 5, flow: 5 });
+    /// ```
+    pub fn get_edge(&self, id: usize) -> FlowEdge {
+        let (from, index) = self.pos[id];
+        let e = self.graph[from][index];
+        let re = self.graph[e.to][e.rev];
+        FlowEdge {
+            from,
+            to: e.to,
+            cap: e.cap + re.cap,
+            flow: re.cap,
+        }
+    }
+
+    /// All added edges in the order of ids
+    pub fn edges(&self) -> Vec<FlowEdge> {
+        (0..self.pos.len()).map(|id| self.get_edge(id)).collect()
+    }
+
+    /// Push as much flow as possible from s to t and return the amount
+    /// ```
+    /// use competitive_tools_rust::max_flow::MaxFlow;
+    /// //   10    2
+    /// // 0 -> 1 -> 3
+    /// // |    |4   ^
+    /// // |    v    |
+    /// // +--> 2 ---+
+    /// //   2     9
+    /// let mut g = MaxFlow::new(4);
+    /// g.add_edge(0, 1, 10);
+    /// g.add_edge(0, 2, 2);
+    /// g.add_edge(1, 2, 4);
+    /// g.add_edge(1, 3, 2);
+    /// g.add_edge(2, 3, 9);
+    /// assert_eq!(g.flow(0, 3), 8);
+    /// let flows: Vec<usize> = g.edges().iter().map(|e| e.flow).collect();
+    /// assert_eq!(flows, vec![6, 2, 4, 2, 6]);
+    /// ```
+    pub fn flow(&mut self, s: usize, t: usize) -> usize {
+        self.flow_with_limit(s, t, usize::MAX)
+    }
+
+    /// Same as flow but stop when the amount reaches limit.
+    /// s and t must be different.
+    /// ```
+    /// use competitive_tools_rust::max_flow::MaxFlow;
+    /// let mut g = MaxFlow::new(3);
+    /// g.add_edge(0, 1, 10);
+    /// g.add_edge(1, 2, 10);
+    /// assert_eq!(g.flow_with_limit(0, 2, 3), 3);
+    /// assert_eq!(g.flow_with_limit(0, 2, 100), 7);
+    /// assert_eq!(g.flow(0, 2), 0);
+    /// ```
+    pub fn flow_with_limit(&mut self, s: usize, t: usize, limit: usize) -> usize {
+        assert_ne!(s, t, "s and t must be different");
+        let n = self.graph.len();
+        let mut total = 0;
+        while total < limit {
+            let level = self.levels(s);
+            if level[t].is_none() {
+                break;
+            }
+            let mut iter = vec![0; n];
+            loop {
+                let pushed = self.augment(s, t, limit - total, &level, &mut iter);
+                if pushed == 0 {
+                    break;
+                }
+                total += pushed;
+                if total == limit {
+                    break;
+                }
+            }
+        }
+        total
+    }
+
+    /// Vertexes reachable from s in the residual graph,
+    /// which is the source side of a minimum cut after flow(s, t)
+    /// ```
+    /// use competitive_tools_rust::max_flow::MaxFlow;
+    /// // project selection: take 0 (+5) and 1 (+3), but 1 requires 2 (-4)
+    /// let profits: Vec<isize> = vec![5, 3, -4];
+    /// let (s, t) = (3, 4);
+    /// let mut g = MaxFlow::new(5);
+    /// for (i, &p) in profits.iter().enumerate() {
+    ///     if p > 0 {
+    ///         g.add_edge(s, i, p as usize);
+    ///     } else {
+    ///         g.add_edge(i, t, (-p) as usize);
+    ///     }
+    /// }
+    /// g.add_edge(1, 2, usize::MAX);
+    /// let cut = g.flow(s, t);
+    /// assert_eq!(8 - cut, 5);
+    /// assert_eq!(g.min_cut(s), vec![0, 3]);
+    /// ```
+    pub fn min_cut(&self, s: usize) -> Vec<usize> {
+        let level = self.levels(s);
+        (0..self.graph.len())
+            .filter(|&v| level[v].is_some())
+            .collect()
+    }
+
+    /// BFS distances from s in the residual graph
+    fn levels(&self, s: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.graph.len()];
+        level[s] = Some(0);
+        let mut que = VecDeque::from(vec![s]);
+        while let Some(v) = que.pop_front() {
+            for e in &self.graph[v] {
+                if e.cap > 0 && level[e.to].is_none() {
+                    level[e.to] = Some(level[v].unwrap() + 1);
+                    que.push_back(e.to);
+                }
+            }
+        }
+        level
+    }
+
+    /// Find one augmenting path along levels without recursion and push flow on it
+    fn augment(
+        &mut self,
+        s: usize,
+        t: usize,
+        limit: usize,
+        level: &[Option<usize>],
+        iter: &mut [usize],
+    ) -> usize {
+        // (vertex, index of the edge) on the current path
+        let mut path: Vec<(usize, usize)> = vec![];
+        let mut v = s;
+        while v != t {
+            let mut advanced = false;
+            while iter[v] < self.graph[v].len() {
+                let e = self.graph[v][iter[v]];
+                if e.cap > 0 && level[e.to].is_some() && level[e.to] == level[v].map(|l| l + 1) {
+                    path.push((v, iter[v]));
+                    v = e.to;
+                    advanced = true;
+                    break;
+                }
+                iter[v] += 1;
+            }
+            if !advanced {
+                // dead end: never visit v again in this phase
+                match path.pop() {
+                    Some((from, _)) => {
+                        iter[from] += 1;
+                        v = from;
+                    }
+                    None => return 0,
+                }
+            }
+        }
+        let pushed = path
+            .iter()
+            .map(|&(u, i)| self.graph[u][i].cap)
+            .min()
+            .unwrap_or(limit)
+            .min(limit);
+        for &(u, i) in &path {
+            let ResidualEdge { to, rev, .. } = self.graph[u][i];
+            self.graph[u][i].cap -= pushed;
+            self.graph[to][rev].cap += pushed;
+        }
+        pushed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::max_flow::MaxFlow;
+    use crate::test_util::Rng;
+
+    /// Naive Ford-Fulkerson on capacity matrix
+    fn naive_max_flow(n: usize, edges: &[(usize, usize, usize)], s: usize, t: usize) -> usize {
+        let mut cap = vec![vec![0; n]; n];
+        for &(u, v, c) in edges {
+            cap[u][v] += c;
+        }
+        let mut total = 0;
+        loop {
+            let mut prev = vec![None; n];
+            prev[s] = Some(s);
+            let mut stack = vec![s];
+            while let Some(u) = stack.pop() {
+                for v in 0..n {
+                    if cap[u][v] > 0 && prev[v].is_none() {
+                        prev[v] = Some(u);
+                        stack.push(v);
+                    }
+                }
+            }
+            if prev[t].is_none() {
+                return total;
+            }
+            let mut v = t;
+            let mut pushed = usize::MAX;
+            while v != s {
+                let u = prev[v].unwrap();
+                pushed = pushed.min(cap[u][v]);
+                v = u;
+            }
+            let mut v = t;
+            while v != s {
+                let u = prev[v].unwrap();
+                cap[u][v] -= pushed;
+                cap[v][u] += pushed;
+                v = u;
+            }
+            total += pushed;
+        }
+    }
+
+    #[test]
+    fn test_flow_with_naive() {
+        let mut rng = Rng::new(1);
+        for _ in 0..200 {
+            let n = 2 + rng.rand(6);
+            let m = rng.rand(15);
+            let edges: Vec<(usize, usize, usize)> = (0..m)
+                .map(|_| (rng.rand(n), rng.rand(n), rng.rand(10)))
+                .collect();
+            let mut g = MaxFlow::new(n);
+            for &(u, v, c) in &edges {
+                g.add_edge(u, v, c);
+            }
+            let flow = g.flow(0, n - 1);
+            assert_eq!(flow, naive_max_flow(n, &edges, 0, n - 1));
+
+            // flow conservation and capacity constraints
+            let mut balance = vec![0isize; n];
+            for e in g.edges() {
+                assert!(e.flow <= e.cap);
+                balance[e.from] -= e.flow as isize;
+                balance[e.to] += e.flow as isize;
+            }
+            for (v, &b) in balance.iter().enumerate() {
+                if v == 0 {
+                    assert_eq!(b, -(flow as isize));
+                } else if v == n - 1 {
+                    assert_eq!(b, flow as isize);
+                } else {
+                    assert_eq!(b, 0);
+                }
+            }
+
+            // capacity of the cut equals the flow
+            let cut = g.min_cut(0);
+            let mut in_cut = vec![false; n];
+            cut.iter().for_each(|&v| in_cut[v] = true);
+            assert!(in_cut[0] && !in_cut[n - 1]);
+            let cut_cap: usize = edges
+                .iter()
+                .filter(|&&(u, v, _)| in_cut[u] && !in_cut[v])
+                .map(|&(_, _, c)| c)
+                .sum();
+            assert_eq!(cut_cap, flow);
+        }
+    }
+
+    #[test]
+    fn test_long_path() {
+        let n = 100_000;
+        let mut g = MaxFlow::new(n);
+        for i in 0..n - 1 {
+            g.add_edge(i, i + 1, 3);
+        }
+        assert_eq!(g.flow(0, n - 1), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "s and t must be different")]
+    fn test_same_source_and_sink() {
+        let mut g = MaxFlow::new(2);
+        g.add_edge(0, 1, 1);
+        g.flow(0, 0);
+    }
+}
@@ -1,4 +1,8 @@
+use crate::graph::Edge;
 use crate::io::parse_line;
+use crate::union_find::UnionFindTree;
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
 
 pub struct Maze<T> {
     rows: usize,
@@ -7,34 +11,21 @@ pub struct Maze<T> {
 }
 
 impl Maze<bool> {
+    /// Turn every true cell connected to (x, y) in 8 directions into false
     pub fn dfs(&mut self, x: usize, y: usize) {
-        if self.field[y][x] {
-            self.field[y][x] = false;
-            for (moved_x, moved_y) in self.movable_positions(x, y) {
-                self.dfs(moved_x, moved_y);
+        let mut stack = vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            if self.field[y][x] {
+                self.field[y][x] = false;
+                stack.extend(self.movable_positions(x, y));
             }
         }
     }
 
     pub fn movable_positions(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
-        let mut positions = vec![];
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                if dx == 0 && dy == 0 {
-                    continue;
-                };
-                if (x == 0 && dx == -1) || (y == 0 && dy == -1) {
-                    continue;
-                };
-                let new_x = (x as isize + dx) as usize;
-                let new_y = (y as isize + dy) as usize;
-                if (self.columns <= new_x) || (self.rows <= new_y) {
-                    continue;
-                };
-                positions.push((new_x, new_y));
-            }
-        }
-        positions
+        adjacent_cells(self.rows, self.columns, y, x, Neighborhood::Eight)
+            .map(|(new_y, new_x)| (new_x, new_y))
+            .collect()
     }
 }
 
@@ -78,10 +69,500 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    /// (d_row, d_column) of one step
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    /// Direction of the step from one cell to an adjacent one
+    /// ```
+    /// use competitive_tools_rust::maze::Direction;
+    /// assert_eq!(Direction::between((1, 1), (0, 1)), Some(Direction::Up));
+    /// assert_eq!(Direction::between((1, 1), (1, 2)), Some(Direction::Right));
+    /// assert_eq!(Direction::between((1, 1), (2, 2)), None);
+    /// ```
+    pub fn between(from: (usize, usize), to: (usize, usize)) -> Option<Direction> {
+        let offset = (
+            to.0 as isize - from.0 as isize,
+            to.1 as isize - from.1 as isize,
+        );
+        [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .find(|d| d.offset() == offset)
+    }
+
+    /// 'U', 'D', 'L' or 'R'
+    pub fn to_char(&self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+}
+
+/// Steps of a path of 4-adjacent cells, None if some step is not 4-adjacent
+/// ```
+/// use competitive_tools_rust::maze::{directions, Direction};
+/// let path = vec![(0, 0), (1, 0), (1, 1)];
+/// assert_eq!(directions(&path), Some(vec![Direction::Down, Direction::Right]));
+/// let udlr: String = directions(&path).unwrap().iter().map(|d| d.to_char()).collect();
+/// assert_eq!(udlr, "DR");
+/// ```
+pub fn directions(path: &[(usize, usize)]) -> Option<Vec<Direction>> {
+    path.windows(2)
+        .map(|w| Direction::between(w[0], w[1]))
+        .collect()
+}
+
+/// Which cells around a cell are adjacent
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// Up, down, left and right
+    Four,
+    /// Four and diagonals
+    Eight,
+}
+
+impl Neighborhood {
+    /// (d_row, d_column) of adjacent cells
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Neighborhood::Eight => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// Cells adjacent to (row, column) inside the rows x columns grid
+fn adjacent_cells(
+    rows: usize,
+    columns: usize,
+    row: usize,
+    column: usize,
+    neighborhood: Neighborhood,
+) -> impl Iterator<Item = (usize, usize)> + use<> {
+    neighborhood.offsets().iter().filter_map(move |&(dr, dc)| {
+        let r = row.checked_add_signed(dr)?;
+        let c = column.checked_add_signed(dc)?;
+        (r < rows && c < columns).then_some((r, c))
+    })
+}
+
+/// Adapter to run graph algorithms on a grid,
+/// numbering the cell (row, column) as the vertex row * columns + column
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GridGraph {
+    pub rows: usize,
+    pub columns: usize,
+    pub neighborhood: Neighborhood,
+}
+
+impl GridGraph {
+    pub fn new(rows: usize, columns: usize, neighborhood: Neighborhood) -> Self {
+        GridGraph {
+            rows,
+            columns,
+            neighborhood,
+        }
+    }
+
+    /// Number of vertexes
+    pub fn len(&self) -> usize {
+        self.rows * self.columns
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// ```
+    /// use competitive_tools_rust::maze::{GridGraph, Neighborhood};
+    /// let grid = GridGraph::new(3, 4, Neighborhood::Four);
+    /// assert_eq!(grid.id(1, 2), 6);
+    /// assert_eq!(grid.cell(6), (1, 2));
+    /// ```
+    pub fn id(&self, row: usize, column: usize) -> usize {
+        row * self.columns + column
+    }
+
+    pub fn cell(&self, id: usize) -> (usize, usize) {
+        (id / self.columns, id % self.columns)
+    }
+
+    /// Cells adjacent to (row, column) inside the grid
+    pub fn adjacent_cells(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + use<> {
+        adjacent_cells(self.rows, self.columns, row, column, self.neighborhood)
+    }
+
+    /// Edges between adjacent passable cells in both directions
+    /// ```
+    /// use competitive_tools_rust::maze::{GridGraph, Neighborhood};
+    /// // .#
+    /// // ..
+    /// let passable = vec![vec![true, false], vec![true, true]];
+    /// let grid = GridGraph::new(2, 2, Neighborhood::Four);
+    /// assert_eq!(grid.adjacency_list(&passable), vec![vec![2], vec![], vec![0, 3], vec![2]]);
+    /// let grid = GridGraph::new(2, 2, Neighborhood::Eight);
+    /// assert_eq!(grid.adjacency_list(&passable)[0], vec![2, 3]);
+    /// ```
+    pub fn adjacency_list(&self, passable: &[Vec<bool>]) -> Vec<Vec<usize>> {
+        (0..self.len())
+            .map(|v| {
+                let (row, column) = self.cell(v);
+                if !passable[row][column] {
+                    return vec![];
+                }
+                self.adjacent_cells(row, column)
+                    .filter(|&(r, c)| passable[r][c])
+                    .map(|(r, c)| self.id(r, c))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Edges for `graph::dijkstra`, where entering the cell (r, c) costs cost\[r\]\[c\]
+    /// and None is a wall
+    /// ```
+    /// use competitive_tools_rust::graph::dijkstra;
+    /// use competitive_tools_rust::maze::{GridGraph, Neighborhood};
+    /// let cost = vec![
+    ///     vec![Some(0), Some(9), Some(1)],
+    ///     vec![Some(1), None, Some(1)],
+    ///     vec![Some(1), Some(1), Some(1)],
+    /// ];
+    /// let grid = GridGraph::new(3, 3, Neighborhood::Four);
+    /// let dists = dijkstra(grid.id(0, 0), grid.len(), &grid.edges_list(&cost));
+    /// assert_eq!(dists[grid.id(0, 2)], Some(6));
+    /// assert_eq!(dists[grid.id(1, 1)], None);
+    /// ```
+    pub fn edges_list(&self, cost: &[Vec<Option<usize>>]) -> Vec<Vec<Edge>> {
+        (0..self.len())
+            .map(|v| {
+                let (row, column) = self.cell(v);
+                if cost[row][column].is_none() {
+                    return vec![];
+                }
+                self.adjacent_cells(row, column)
+                    .filter_map(|(r, c)| {
+                        cost[r][c].map(|cost| Edge {
+                            to: self.id(r, c),
+                            cost,
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Union find tree uniting adjacent passable cells
+    /// ```
+    /// use competitive_tools_rust::maze::{GridGraph, Neighborhood};
+    /// // .#.
+    /// // #.#
+    /// let passable = vec![vec![true, false, true], vec![false, true, false]];
+    /// let grid = GridGraph::new(2, 3, Neighborhood::Four);
+    /// let mut tree = grid.union_find(&passable);
+    /// assert!(!tree.is_same(grid.id(0, 0), grid.id(1, 1)));
+    /// let grid = GridGraph::new(2, 3, Neighborhood::Eight);
+    /// let mut tree = grid.union_find(&passable);
+    /// assert!(tree.is_same(grid.id(0, 0), grid.id(0, 2)));
+    /// ```
+    pub fn union_find(&self, passable: &[Vec<bool>]) -> UnionFindTree {
+        let mut tree = UnionFindTree::new(self.len());
+        for (v, adjacent) in self.adjacency_list(passable).iter().enumerate() {
+            for &to in adjacent {
+                tree.unite(v, to);
+            }
+        }
+        tree
+    }
+}
+
+/// Rectangular grid of cells indexed by (row, column)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    rows: usize,
+    columns: usize,
+    field: Vec<Vec<T>>,
+}
+
+impl<T> Grid<T> {
+    /// field must be rectangular
+    pub fn new(field: Vec<Vec<T>>) -> Self {
+        let rows = field.len();
+        let columns = field.first().map_or(0, |line| line.len());
+        assert!(
+            field.iter().all(|line| line.len() == columns),
+            "field must be rectangular"
+        );
+        Grid {
+            rows,
+            columns,
+            field,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Cell at signed (row, column), None if outside the grid
+    /// ```
+    /// use competitive_tools_rust::maze::Grid;
+    /// let grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+    /// let (r, c) = (1, 0);
+    /// assert_eq!(grid.get(r - 1, c + 1), Some(&2));
+    /// assert_eq!(grid.get(r, c - 1), None);
+    /// assert_eq!(grid.get(r + 1, c), None);
+    /// ```
+    pub fn get(&self, row: isize, column: isize) -> Option<&T> {
+        if row < 0 || column < 0 {
+            return None;
+        }
+        self.field.get(row as usize)?.get(column as usize)
+    }
+
+    /// Up, down, left and right cells inside the grid
+    /// ```
+    /// use competitive_tools_rust::maze::Grid;
+    /// let grid = Grid::new(vec![vec![0; 3]; 2]);
+    /// assert_eq!(grid.neighbors4(0, 1).collect::<Vec<_>>(), vec![(1, 1), (0, 0), (0, 2)]);
+    /// ```
+    pub fn neighbors4(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        adjacent_cells(self.rows, self.columns, row, column, Neighborhood::Four)
+    }
+
+    /// Surrounding 8 cells inside the grid
+    /// ```
+    /// use competitive_tools_rust::maze::Grid;
+    /// let grid = Grid::new(vec![vec![0; 3]; 2]);
+    /// assert_eq!(grid.neighbors8(0, 0).collect::<Vec<_>>(), vec![(0, 1), (1, 0), (1, 1)]);
+    /// assert_eq!(grid.neighbors8(1, 1).count(), 5);
+    /// ```
+    pub fn neighbors8(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        adjacent_cells(self.rows, self.columns, row, column, Neighborhood::Eight)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> + use<'_, T> {
+        self.field.iter().map(|line| line.as_slice())
+    }
+
+    /// ```
+    /// use competitive_tools_rust::maze::Grid;
+    /// let grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+    /// assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&2, &4]);
+    /// assert_eq!(grid.iter_rows().map(|row| row.iter().sum::<i32>()).collect::<Vec<_>>(), vec![3, 7]);
+    /// ```
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> + use<'_, T> {
+        self.field.iter().map(move |line| &line[column])
+    }
+
+    /// All (row, column) in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let columns = self.columns;
+        (0..self.rows).flat_map(move |r| (0..columns).map(move |c| (r, c)))
+    }
+
+    /// First (row, column) in row-major order whose cell satisfies predicate
+    /// ```
+    /// use competitive_tools_rust::maze::Grid;
+    /// let grid = Grid::from_char_lines(&["S.#", "..G"]);
+    /// assert_eq!(grid.position(|&ch| ch == 'G'), Some((1, 2)));
+    /// ```
+    pub fn position<P: Fn(&T) -> bool>(&self, predicate: P) -> Option<(usize, usize)> {
+        self.positions()
+            .find(|&(r, c)| predicate(&self.field[r][c]))
+    }
+
+    pub fn into_field(self) -> Vec<Vec<T>> {
+        self.field
+    }
+}
+
+/// Result of BFS on a grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridBfs {
+    /// Number of steps from the nearest source, None if unreachable
+    pub dist: Vec<Vec<Option<usize>>>,
+    /// Previous cell on a shortest path, None for sources and unreachable cells
+    prev: Vec<Vec<Option<(usize, usize)>>>,
+}
+
+impl GridBfs {
+    /// Shortest path from the nearest source to goal, None if unreachable
+    pub fn path_to(&self, goal: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+        self.dist[goal.0][goal.1]?;
+        let mut path = vec![goal];
+        let mut cell = goal;
+        while let Some(prev) = self.prev[cell.0][cell.1] {
+            path.push(prev);
+            cell = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<T> Grid<T> {
+    /// BFS from all sources at once, moving between passable cells of the neighborhood
+    /// ```
+    /// use competitive_tools_rust::maze::{directions, Grid, Neighborhood};
+    /// let grid = Grid::from_char_lines(&[
+    ///     "S.#",
+    ///     "#..",
+    ///     "G.#",
+    /// ]);
+    /// let start = grid.position(|&ch| ch == 'S').unwrap();
+    /// let goal = grid.position(|&ch| ch == 'G').unwrap();
+    /// let bfs = grid.bfs(&[start], Neighborhood::Four, |&ch| ch != '#');
+    /// assert_eq!(bfs.dist, vec![
+    ///     vec![Some(0), Some(1), None],
+    ///     vec![None, Some(2), Some(3)],
+    ///     vec![Some(4), Some(3), None],
+    /// ]);
+    /// let path = bfs.path_to(goal).unwrap();
+    /// assert_eq!(path, vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]);
+    /// let udlr: String = directions(&path).unwrap().iter().map(|d| d.to_char()).collect();
+    /// assert_eq!(udlr, "RDDL");
+    /// ```
+    pub fn bfs<P: Fn(&T) -> bool>(
+        &self,
+        sources: &[(usize, usize)],
+        neighborhood: Neighborhood,
+        passable: P,
+    ) -> GridBfs {
+        let mut dist = vec![vec![None; self.columns]; self.rows];
+        let mut prev = vec![vec![None; self.columns]; self.rows];
+        let mut que = VecDeque::new();
+        for &(r, c) in sources {
+            if dist[r][c].is_none() {
+                dist[r][c] = Some(0);
+                que.push_back((r, c));
+            }
+        }
+        while let Some((r, c)) = que.pop_front() {
+            let d = dist[r][c].unwrap();
+            for (nr, nc) in adjacent_cells(self.rows, self.columns, r, c, neighborhood) {
+                if dist[nr][nc].is_none() && passable(&self.field[nr][nc]) {
+                    dist[nr][nc] = Some(d + 1);
+                    prev[nr][nc] = Some((r, c));
+                    que.push_back((nr, nc));
+                }
+            }
+        }
+        GridBfs { dist, prev }
+    }
+}
+
+impl Grid<bool> {
+    /// Read h lines from stdin like `parse_maze`, true for cells other than wall_ch
+    pub fn parse(h: usize, wall_ch: char) -> Self {
+        Grid::new(parse_maze(h, wall_ch))
+    }
+
+    /// ```
+    /// use competitive_tools_rust::maze::Grid;
+    /// let grid = Grid::from_lines(&[".#", ".."], '#');
+    /// assert_eq!(grid[(0, 1)], false);
+    /// assert_eq!(grid[(1, 1)], true);
+    /// ```
+    pub fn from_lines<S: AsRef<str>>(lines: &[S], wall_ch: char) -> Self {
+        Grid::new(
+            lines
+                .iter()
+                .map(|line| line.as_ref().chars().map(|ch| ch != wall_ch).collect())
+                .collect(),
+        )
+    }
+}
+
+impl Grid<char> {
+    /// Read h lines from stdin as characters
+    pub fn parse_chars(h: usize) -> Self {
+        Grid::new(
+            (0..h)
+                .map(|_| parse_line::<String>().chars().collect())
+                .collect(),
+        )
+    }
+
+    pub fn from_char_lines<S: AsRef<str>>(lines: &[S]) -> Self {
+        Grid::new(
+            lines
+                .iter()
+                .map(|line| line.as_ref().chars().collect())
+                .collect(),
+        )
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        &self.field[row][column]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        &mut self.field[row][column]
+    }
+}
+
+impl<T> From<Grid<T>> for Maze<T> {
+    /// Maze whose field\[y\]\[x\] is grid\[(y, x)\]
+    fn from(grid: Grid<T>) -> Self {
+        Maze {
+            rows: grid.rows,
+            columns: grid.columns,
+            field: grid.field,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests_maze {
     //use crate::d;
-    use crate::maze::Maze;
+    use crate::maze::{Grid, GridGraph, Maze, Neighborhood, directions};
+    use std::collections::HashSet;
 
     #[test]
     fn test_poj2386() {
@@ -123,4 +604,138 @@ mod tests_maze {
         }
         assert_eq!(ans, 3);
     }
+
+    #[test]
+    fn test_dfs_for_deep_maze() {
+        let rows = 1000;
+        let columns = 1000;
+        // snake shaped path which visits all cells in one stroke
+        let field: Vec<Vec<bool>> = (0..rows)
+            .map(|y| {
+                (0..columns)
+                    .map(|x| match y % 4 {
+                        1 => x == columns - 1,
+                        3 => x == 0,
+                        _ => true,
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut maze: Maze<bool> = Maze {
+            rows,
+            columns,
+            field,
+        };
+        maze.dfs(0, 0);
+        assert!(maze.field.iter().all(|line| line.iter().all(|&i| !i)));
+    }
+
+    #[test]
+    fn test_grid_graph_components_with_maze_dfs() {
+        let input_lines = [
+            "W........WW.",
+            ".WWW.....WWW",
+            "....WW...WW.",
+            ".........WW.",
+            ".........W..",
+            "..W......W..",
+            ".W.W.....WW.",
+            "W.W.W.....W.",
+            ".W.W......W.",
+            "..W.......W.",
+        ];
+        let passable: Vec<Vec<bool>> = input_lines
+            .iter()
+            .map(|line| line.chars().map(|c| c == 'W').collect())
+            .collect();
+        let grid = GridGraph::new(10, 12, Neighborhood::Eight);
+        let mut tree = grid.union_find(&passable);
+        let mut maze: Maze<bool> = Maze {
+            rows: 10,
+            columns: 12,
+            field: passable.clone(),
+        };
+        let mut components = 0;
+        for v in 0..grid.len() {
+            let (r, c) = grid.cell(v);
+            if !maze.field[r][c] {
+                continue;
+            }
+            // the cells cleared by dfs are exactly the component of v in the union find
+            let before = maze.field.clone();
+            maze.dfs(c, r);
+            components += 1;
+            for u in 0..grid.len() {
+                let (ur, uc) = grid.cell(u);
+                let cleared = before[ur][uc] && !maze.field[ur][uc];
+                assert_eq!(cleared, tree.is_same(u, v));
+            }
+        }
+        let roots: HashSet<usize> = (0..grid.len())
+            .filter(|&v| {
+                let (r, c) = grid.cell(v);
+                passable[r][c]
+            })
+            .map(|v| tree.root(v))
+            .collect();
+        assert_eq!(roots.len(), components);
+        let grid = GridGraph::new(10, 12, Neighborhood::Four);
+        let adjacency_list = grid.adjacency_list(&passable);
+        for (v, adjacent) in adjacency_list.iter().enumerate() {
+            for &to in adjacent {
+                assert!(adjacency_list[to].contains(&v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_neighbors_with_get() {
+        let grid = Grid::new((0..4).map(|r| (0..5).map(|c| (r, c)).collect()).collect());
+        for (r, c) in grid.positions() {
+            let (r_signed, c_signed) = (r as isize, c as isize);
+            let mut expected4 = vec![];
+            let mut expected8 = vec![];
+            for dr in -1..=1 {
+                for dc in -1..=1 {
+                    if let Some(&cell) = grid.get(r_signed + dr, c_signed + dc)
+                        && (dr, dc) != (0, 0)
+                    {
+                        expected8.push(cell);
+                        if dr == 0 || dc == 0 {
+                            expected4.push(cell);
+                        }
+                    }
+                }
+            }
+            let mut neighbors4: Vec<(usize, usize)> = grid.neighbors4(r, c).collect();
+            neighbors4.sort();
+            assert_eq!(neighbors4, expected4);
+            assert_eq!(grid.neighbors8(r, c).collect::<Vec<_>>(), expected8);
+            assert_eq!(grid[(r, c)], (r, c));
+        }
+        let maze: Maze<(usize, usize)> = grid.into();
+        assert_eq!(maze.field[3][4], (3, 4));
+    }
+
+    #[test]
+    fn test_multi_source_bfs() {
+        // distance to the nearest '#' in 4 directions, walls do not block
+        let grid = Grid::from_char_lines(&["#....", ".....", "....#"]);
+        let sources: Vec<(usize, usize)> = grid.positions().filter(|&p| grid[p] == '#').collect();
+        let bfs = grid.bfs(&sources, Neighborhood::Four, |_| true);
+        for (r, c) in grid.positions() {
+            let expected = sources
+                .iter()
+                .map(|&(sr, sc)| sr.abs_diff(r) + sc.abs_diff(c))
+                .min();
+            assert_eq!(bfs.dist[r][c], expected);
+            let path = bfs.path_to((r, c)).unwrap();
+            assert!(sources.contains(&path[0]));
+            assert_eq!(path.len(), expected.unwrap() + 1);
+            assert_eq!(directions(&path).unwrap().len(), expected.unwrap());
+        }
+        let bfs = grid.bfs(&[(0, 0)], Neighborhood::Eight, |_| true);
+        assert_eq!(bfs.dist[2][4], Some(4));
+        assert_eq!(directions(&bfs.path_to((2, 4)).unwrap()), None);
+    }
 }
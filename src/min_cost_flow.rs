@@ -0,0 +1,289 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Edge of MinCostFlow after (or before) running flow
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CostFlowEdge {
+    pub from: usize,
+    pub to: usize,
+    pub cap: usize,
+    pub flow: usize,
+    pub cost: isize,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ResidualEdge {
+    to: usize,
+    /// index of the reverse edge in graph\[to\]
+    rev: usize,
+    cap: usize,
+    cost: isize,
+}
+
+/// Minimum cost flow by primal-dual method
+/// (Dijkstra with potentials, initialized by Bellman-Ford when some costs are negative)
+/// The graph must not have negative cycles.
+pub struct MinCostFlow {
+    graph: Vec<Vec<ResidualEdge>>,
+    /// (from, index in graph\[from\]) of each added edge
+    pos: Vec<(usize, usize)>,
+}
+
+impl MinCostFlow {
+    pub fn new(n: usize) -> Self {
+        MinCostFlow {
+            graph: vec![vec![]; n],
+            pos: vec![],
+        }
+    }
+
+    /// Add directed edge and return its id
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: usize, cost: isize) -> usize {
+        let id = self.pos.len();
+        let from_index = self.graph[from].len();
+        let to_index = self.graph[to].len() + if from == to { 1 } else { 0 };
+        self.pos.push((from, from_index));
+        self.graph[from].push(ResidualEdge {
+            to,
+            rev: to_index,
+            cap,
+            cost,
+        });
+        self.graph[to].push(ResidualEdge {
+            to: from,
+            rev: from_index,
+            cap: 0,
+            cost: -cost,
+        });
+        id
+    }
+
+    pub fn get_edge(&self, id: usize) -> CostFlowEdge {
+        let (from, index) = self.pos[id];
+        let e = self.graph[from][index];
+        let re = self.graph[e.to][e.rev];
+        CostFlowEdge {
+            from,
+            to: e.to,
+            cap: e.cap + re.cap,
+            flow: re.cap,
+            cost: e.cost,
+        }
+    }
+
+    /// All added edges in the order of ids
+    pub fn edges(&self) -> Vec<CostFlowEdge> {
+        (0..self.pos.len()).map(|id| self.get_edge(id)).collect()
+    }
+
+    /// Push flow from s to t up to limit with minimum cost
+    /// and return (amount of flow, its cost). s and t must be different.
+    /// ```
+    /// use competitive_tools_rust::min_cost_flow::MinCostFlow;
+    /// // assignment: worker i does job j with cost[i][j]
+    /// let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+    /// let (s, t) = (6, 7);
+    /// let mut g = MinCostFlow::new(8);
+    /// for i in 0..3 {
+    ///     g.add_edge(s, i, 1, 0);
+    ///     g.add_edge(3 + i, t, 1, 0);
+    ///     for j in 0..3 {
+    ///         g.add_edge(i, 3 + j, 1, cost[i][j]);
+    ///     }
+    /// }
+    /// assert_eq!(g.flow(s, t, usize::MAX), (3, 5));
+    /// let assigned: Vec<(usize, usize)> = g
+    ///     .edges()
+    ///     .iter()
+    ///     .filter(|e| e.flow > 0 && e.from < 3)
+    ///     .map(|e| (e.from, e.to - 3))
+    ///     .collect();
+    /// assert_eq!(assigned, vec![(0, 1), (1, 0), (2, 2)]);
+    /// ```
+    pub fn flow(&mut self, s: usize, t: usize, limit: usize) -> (usize, isize) {
+        *self.slope(s, t, limit).last().unwrap()
+    }
+
+    /// Breakpoints of the cost as a function of the amount of flow,
+    /// which is convex and piecewise-linear.
+    /// The first item is (0, 0) and the last one is the result of flow.
+    /// ```
+    /// use competitive_tools_rust::min_cost_flow::MinCostFlow;
+    /// // 0 -> 1 costs 1 for 2 units and 0 -> 2 -> 1 costs 3 for 3 units
+    /// let mut g = MinCostFlow::new(3);
+    /// g.add_edge(0, 1, 2, 1);
+    /// g.add_edge(0, 2, 3, 1);
+    /// g.add_edge(2, 1, 4, 2);
+    /// assert_eq!(g.slope(0, 1, 10), vec![(0, 0), (2, 2), (5, 11)]);
+    /// ```
+    pub fn slope(&mut self, s: usize, t: usize, limit: usize) -> Vec<(usize, isize)> {
+        assert_ne!(s, t, "s and t must be different");
+        let n = self.graph.len();
+        let mut potential = self.initial_potential(s);
+        let mut result = vec![(0, 0)];
+        let mut flow = 0;
+        let mut cost = 0;
+        let mut prev_unit_cost = None;
+        while flow < limit {
+            // Dijkstra on reduced costs: cost + potential[from] - potential[to] >= 0
+            let mut dist: Vec<Option<isize>> = vec![None; n];
+            let mut visited = vec![false; n];
+            let mut prev_edge: Vec<Option<(usize, usize)>> = vec![None; n];
+            let mut que = BinaryHeap::new();
+            dist[s] = Some(0);
+            que.push(Reverse((0, s)));
+            while let Some(Reverse((d, v))) = que.pop() {
+                if visited[v] {
+                    continue;
+                }
+                visited[v] = true;
+                if v == t {
+                    break;
+                }
+                for (i, e) in self.graph[v].iter().enumerate() {
+                    if e.cap == 0 || visited[e.to] {
+                        continue;
+                    }
+                    let candidate_dist = d + e.cost + potential[v] - potential[e.to];
+                    if dist[e.to].is_none_or(|to_dist| candidate_dist < to_dist) {
+                        dist[e.to] = Some(candidate_dist);
+                        prev_edge[e.to] = Some((v, i));
+                        que.push(Reverse((candidate_dist, e.to)));
+                    }
+                }
+            }
+            if !visited[t] {
+                break;
+            }
+            let dist_t = dist[t].unwrap();
+            for v in 0..n {
+                if visited[v] {
+                    potential[v] += dist[v].unwrap() - dist_t;
+                }
+            }
+
+            let mut pushed = limit - flow;
+            let mut unit_cost = 0;
+            let mut v = t;
+            while let Some((from, i)) = prev_edge[v] {
+                pushed = pushed.min(self.graph[from][i].cap);
+                unit_cost += self.graph[from][i].cost;
+                v = from;
+            }
+            let mut v = t;
+            while let Some((from, i)) = prev_edge[v] {
+                let ResidualEdge { to, rev, .. } = self.graph[from][i];
+                self.graph[from][i].cap -= pushed;
+                self.graph[to][rev].cap += pushed;
+                v = from;
+            }
+            flow += pushed;
+            cost += unit_cost * pushed as isize;
+            if prev_unit_cost == Some(unit_cost) {
+                result.pop();
+            }
+            result.push((flow, cost));
+            prev_unit_cost = Some(unit_cost);
+        }
+        result
+    }
+
+    /// Shortest distances from s by Bellman-Ford when some costs are negative
+    fn initial_potential(&self, s: usize) -> Vec<isize> {
+        let n = self.graph.len();
+        let mut potential = vec![0; n];
+        let has_negative = self.graph.iter().flatten().any(|e| e.cap > 0 && e.cost < 0);
+        if !has_negative {
+            return potential;
+        }
+        let mut dist: Vec<Option<isize>> = vec![None; n];
+        dist[s] = Some(0);
+        for _ in 0..n {
+            let mut updated = false;
+            for v in 0..n {
+                let Some(d) = dist[v] else { continue };
+                for e in &self.graph[v] {
+                    if e.cap > 0 && dist[e.to].is_none_or(|to_dist| d + e.cost < to_dist) {
+                        dist[e.to] = Some(d + e.cost);
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+        }
+        for v in 0..n {
+            if let Some(d) = dist[v] {
+                potential[v] = d;
+            }
+        }
+        potential
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::min_cost_flow::MinCostFlow;
+    use crate::test_util::Rng;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_assignment_with_permutations() {
+        let mut rng = Rng::new(3);
+        for _ in 0..50 {
+            let n = 1 + rng.rand(5);
+            let cost: Vec<Vec<isize>> = (0..n)
+                .map(|_| (0..n).map(|_| rng.rand(21) as isize - 10).collect())
+                .collect();
+            let naive = (0..n)
+                .permutations(n)
+                .map(|p| (0..n).map(|i| cost[i][p[i]]).sum::<isize>())
+                .min()
+                .unwrap();
+            let (s, t) = (2 * n, 2 * n + 1);
+            let mut g = MinCostFlow::new(2 * n + 2);
+            for (i, row) in cost.iter().enumerate() {
+                g.add_edge(s, i, 1, 0);
+                g.add_edge(n + i, t, 1, 0);
+                for (j, &c) in row.iter().enumerate() {
+                    g.add_edge(i, n + j, 1, c);
+                }
+            }
+            assert_eq!(g.flow(s, t, usize::MAX), (n, naive));
+        }
+    }
+
+    #[test]
+    fn test_slope_is_convex() {
+        let mut g = MinCostFlow::new(4);
+        g.add_edge(0, 1, 3, -2);
+        g.add_edge(0, 2, 2, 1);
+        g.add_edge(1, 3, 2, 4);
+        g.add_edge(2, 3, 5, 1);
+        g.add_edge(1, 2, 4, -1);
+        let slope = g.slope(0, 3, 100);
+        assert_eq!(slope, vec![(0, 0), (3, -6), (5, -2)]);
+        for w in slope.windows(3) {
+            let d1 = (w[1].1 - w[0].1) * (w[2].0 - w[1].0) as isize;
+            let d2 = (w[2].1 - w[1].1) * (w[1].0 - w[0].0) as isize;
+            assert!(d1 < d2);
+        }
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let mut g = MinCostFlow::new(3);
+        g.add_edge(0, 1, 5, 2);
+        assert_eq!(g.flow(0, 2, 10), (0, 0));
+        assert_eq!(g.slope(0, 1, 3), vec![(0, 0), (3, 6)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "s and t must be different")]
+    fn test_same_source_and_sink() {
+        let mut g = MinCostFlow::new(2);
+        g.add_edge(0, 1, 1, 1);
+        g.flow(1, 1, 10);
+    }
+}
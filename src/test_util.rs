@@ -0,0 +1,47 @@
+//! Helpers shared by the unit tests
+
+/// Linear congruential generator for reproducible random cases
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    /// Next raw state
+    pub fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0
+    }
+
+    /// Uniform-ish value in 0..m
+    pub fn rand(&mut self, m: usize) -> usize {
+        (self.next() >> 33) as usize % m
+    }
+}
+
+/// 0 - 1 - ... - (n - 1) as an undirected adjacency list
+pub fn path_graph(n: usize) -> Vec<Vec<usize>> {
+    (0..n)
+        .map(|i| {
+            let mut adj = vec![];
+            if i > 0 {
+                adj.push(i - 1);
+            }
+            if i + 1 < n {
+                adj.push(i + 1);
+            }
+            adj
+        })
+        .collect()
+}
+
+/// 0 -> 1 -> ... -> (n - 1)
+pub fn directed_path_graph(n: usize) -> Vec<Vec<usize>> {
+    (0..n)
+        .map(|i| if i + 1 < n { vec![i + 1] } else { vec![] })
+        .collect()
+}
@@ -0,0 +1,364 @@
+use crate::graph::{Edge, Graph, Weight};
+use std::collections::HashMap;
+
+/// Arrays of a tree rooted at some vertex
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RootedTree {
+    pub parent: Vec<Option<usize>>,
+    /// Number of edges from the root
+    pub depth: Vec<usize>,
+    /// Sum of weights from the root (same as depth when unweighted)
+    pub dist: Vec<usize>,
+    /// BFS order, so parents come before their children
+    pub order: Vec<usize>,
+    pub subtree_size: Vec<usize>,
+}
+
+/// Longest path of a tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diameter {
+    pub length: usize,
+    /// Vertexes on the path from one end to the other
+    pub path: Vec<usize>,
+}
+
+/// Tree given as `Vec<Vec<usize>>` (unit weights) or `Vec<Vec<Edge>>` (weighted),
+/// where each edge appears in the lists of both ends.
+pub trait Tree {
+    /// (to, weight) of edges from v
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = (usize, usize)> + '_;
+    fn vertex_count(&self) -> usize;
+
+    /// ```
+    /// use competitive_tools_rust::tree::Tree;
+    /// // 0 - 1 - 2
+    /// //     |
+    /// //     3
+    /// let tree: Vec<Vec<usize>> = vec![vec![1], vec![0, 2, 3], vec![1], vec![1]];
+    /// let rooted = tree.rooted(2);
+    /// assert_eq!(rooted.parent, vec![Some(1), Some(2), None, Some(1)]);
+    /// assert_eq!(rooted.depth, vec![2, 1, 0, 2]);
+    /// assert_eq!(rooted.order, vec![2, 1, 0, 3]);
+    /// assert_eq!(rooted.subtree_size, vec![1, 3, 4, 1]);
+    /// ```
+    fn rooted(&self, root: usize) -> RootedTree {
+        let n = self.vertex_count();
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut dist = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        order.push(root);
+        let mut i = 0;
+        while i < order.len() {
+            let v = order[i];
+            for (to, weight) in self.neighbors(v) {
+                if Some(to) != parent[v] && to != root {
+                    parent[to] = Some(v);
+                    depth[to] = depth[v] + 1;
+                    dist[to] = dist[v] + weight;
+                    order.push(to);
+                }
+            }
+            i += 1;
+        }
+        let mut subtree_size = vec![1; n];
+        for &v in order.iter().rev() {
+            if let Some(p) = parent[v] {
+                subtree_size[p] += subtree_size[v];
+            }
+        }
+        RootedTree {
+            parent,
+            depth,
+            dist,
+            order,
+            subtree_size,
+        }
+    }
+
+    /// ```
+    /// use competitive_tools_rust::graph::Edge;
+    /// use competitive_tools_rust::tree::{Diameter, Tree};
+    /// // 0 - 1 - 2
+    /// //     |
+    /// //     3 - 4
+    /// let tree: Vec<Vec<usize>> = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    /// assert_eq!(tree.diameter(), Diameter { length: 3, path: vec![0, 1, 3, 4] });
+    ///
+    /// // 0 -5- 1 -1- 2
+    /// let weighted: Vec<Vec<Edge>> = vec![
+    ///     vec![Edge { to: 1, cost: 5 }],
+    ///     vec![Edge { to: 0, cost: 5 }, Edge { to: 2, cost: 1 }],
+    ///     vec![Edge { to: 1, cost: 1 }],
+    /// ];
+    /// assert_eq!(weighted.diameter(), Diameter { length: 6, path: vec![0, 1, 2] });
+    /// ```
+    fn diameter(&self) -> Diameter {
+        let from_zero = self.rooted(0);
+        let a = farthest(&from_zero.dist);
+        let from_a = self.rooted(a);
+        let b = farthest(&from_a.dist);
+        let mut path = vec![b];
+        let mut v = b;
+        while let Some(p) = from_a.parent[v] {
+            path.push(p);
+            v = p;
+        }
+        Diameter {
+            length: from_a.dist[b],
+            path,
+        }
+    }
+
+    /// Distance to the farthest vertex from each vertex
+    /// ```
+    /// use competitive_tools_rust::tree::Tree;
+    /// // 0 - 1 - 2
+    /// //     |
+    /// //     3 - 4
+    /// let tree: Vec<Vec<usize>> = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    /// assert_eq!(tree.farthest_distances(), vec![3, 2, 3, 2, 3]);
+    /// ```
+    fn farthest_distances(&self) -> Vec<usize> {
+        // the farthest vertex from any vertex is one of the ends of a diameter
+        let diameter = self.diameter();
+        let from_a = self.rooted(diameter.path[0]);
+        let from_b = self.rooted(*diameter.path.last().unwrap());
+        from_a
+            .dist
+            .iter()
+            .zip(from_b.dist.iter())
+            .map(|(&a, &b)| a.max(b))
+            .collect()
+    }
+
+    /// Vertexes minimizing the distance to the farthest vertex (one or two when unweighted)
+    /// ```
+    /// use competitive_tools_rust::tree::Tree;
+    /// // 0 - 1 - 2
+    /// //     |
+    /// //     3 - 4
+    /// let tree: Vec<Vec<usize>> = vec![vec![1], vec![0, 2, 3], vec![1], vec![1, 4], vec![3]];
+    /// assert_eq!(tree.centers(), vec![1, 3]);
+    /// let tree: Vec<Vec<usize>> = vec![vec![1], vec![0, 2], vec![1]];
+    /// assert_eq!(tree.centers(), vec![1]);
+    /// ```
+    fn centers(&self) -> Vec<usize> {
+        let farthest_distances = self.farthest_distances();
+        let min = *farthest_distances.iter().min().unwrap();
+        (0..farthest_distances.len())
+            .filter(|&v| farthest_distances[v] == min)
+            .collect()
+    }
+}
+
+fn farthest(dist: &[usize]) -> usize {
+    (0..dist.len())
+        .max_by_key(|&v| (dist[v], usize::MAX - v))
+        .unwrap()
+}
+
+impl Tree for Vec<Vec<usize>> {
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self[v].iter().map(|&to| (to, 1))
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Tree for Vec<Vec<Edge>> {
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self[v].iter().map(|e| (e.to, e.cost))
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Only for undirected graphs (panics on directed ones)
+impl<W: Weight> Tree for Graph<W> {
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.assert_undirected();
+        self.edges_from(v).iter().map(|&(to, w)| (to, w.cost()))
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+}
+
+/// AHU canonical ids of rooted trees: two rooted (sub)trees get the same id
+/// if and only if they are isomorphic. Ids are shared by all trees given to the same instance,
+/// so use one instance to compare several trees. Edge weights are ignored.
+#[derive(Clone, Debug, Default)]
+pub struct TreeIsomorphism {
+    /// sorted ids of children -> id
+    dictionary: HashMap<Vec<usize>, usize>,
+}
+
+impl TreeIsomorphism {
+    pub fn new() -> Self {
+        TreeIsomorphism::default()
+    }
+
+    /// Id of the subtree rooted at each vertex when the tree is rooted at root, in O(n log n)
+    /// ```
+    /// use competitive_tools_rust::tree::TreeIsomorphism;
+    /// // 0 - 1 - 2
+    /// // |
+    /// // 3 - 4
+    /// let tree: Vec<Vec<usize>> = vec![vec![1, 3], vec![0, 2], vec![1], vec![0, 4], vec![3]];
+    /// let mut isomorphism = TreeIsomorphism::new();
+    /// let ids = isomorphism.subtree_ids(&tree, 0);
+    /// assert_eq!(ids[1], ids[3]);
+    /// assert_eq!(ids[2], ids[4]);
+    /// assert_ne!(ids[0], ids[1]);
+    /// ```
+    pub fn subtree_ids(&mut self, tree: &impl Tree, root: usize) -> Vec<usize> {
+        let rooted = tree.rooted(root);
+        let n = tree.vertex_count();
+        let mut children_ids: Vec<Vec<usize>> = vec![vec![]; n];
+        let mut ids = vec![0; n];
+        for &v in rooted.order.iter().rev() {
+            let mut key = std::mem::take(&mut children_ids[v]);
+            key.sort_unstable();
+            let next_id = self.dictionary.len();
+            ids[v] = *self.dictionary.entry(key).or_insert(next_id);
+            if let Some(p) = rooted.parent[v] {
+                children_ids[p].push(ids[v]);
+            }
+        }
+        ids
+    }
+
+    /// Id of the tree rooted at root
+    pub fn rooted_id(&mut self, tree: &impl Tree, root: usize) -> usize {
+        self.subtree_ids(tree, root)[root]
+    }
+
+    /// Id of an unrooted tree with at least one vertex, rooted at its center(s).
+    /// Two unrooted trees get the same id if and only if they are isomorphic.
+    /// ```
+    /// use competitive_tools_rust::tree::TreeIsomorphism;
+    /// // 0 - 1 - 2 - 3 and 3 - 0 - 2 - 1 are the same path
+    /// let a: Vec<Vec<usize>> = vec![vec![1], vec![0, 2], vec![1, 3], vec![2]];
+    /// let b: Vec<Vec<usize>> = vec![vec![3, 2], vec![2], vec![0, 1], vec![0]];
+    /// // star with 3 leaves
+    /// let c: Vec<Vec<usize>> = vec![vec![1, 2, 3], vec![0], vec![0], vec![0]];
+    /// let mut isomorphism = TreeIsomorphism::new();
+    /// assert_eq!(isomorphism.unrooted_id(&a), isomorphism.unrooted_id(&b));
+    /// assert_ne!(isomorphism.unrooted_id(&a), isomorphism.unrooted_id(&c));
+    /// ```
+    pub fn unrooted_id(&mut self, tree: &impl Tree) -> usize {
+        // centers by the number of edges even for weighted trees
+        let unweighted: Vec<Vec<usize>> = (0..tree.vertex_count())
+            .map(|v| tree.neighbors(v).map(|(to, _)| to).collect())
+            .collect();
+        unweighted
+            .centers()
+            .into_iter()
+            .map(|c| self.rooted_id(&unweighted, c))
+            .min()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Edge, Graph, dijkstra};
+    use crate::test_util::Rng;
+    use crate::tree::{Tree, TreeIsomorphism};
+    use itertools::Itertools;
+
+    fn random_weighted_tree(n: usize, rng: &mut Rng) -> Vec<Vec<Edge>> {
+        let mut tree = vec![vec![]; n];
+        for v in 1..n {
+            let state = rng.next();
+            let p = (state >> 33) as usize % v;
+            let cost = (state >> 20) as usize % 10;
+            tree[v].push(Edge { to: p, cost });
+            tree[p].push(Edge { to: v, cost });
+        }
+        tree
+    }
+
+    #[test]
+    fn test_with_dijkstra() {
+        let mut rng = Rng::new(31);
+        for n in 1..30 {
+            let tree = random_weighted_tree(n, &mut rng);
+            let all_dists: Vec<Vec<usize>> = (0..n)
+                .map(|s| {
+                    dijkstra(s, n, &tree)
+                        .into_iter()
+                        .map(|d| d.unwrap())
+                        .collect()
+                })
+                .collect();
+            let farthest: Vec<usize> = all_dists.iter().map(|d| *d.iter().max().unwrap()).collect();
+            assert_eq!(tree.farthest_distances(), farthest);
+            let diameter = tree.diameter();
+            assert_eq!(diameter.length, *farthest.iter().max().unwrap());
+            let (a, b) = (diameter.path[0], *diameter.path.last().unwrap());
+            assert_eq!(all_dists[a][b], diameter.length);
+            let min = *farthest.iter().min().unwrap();
+            for c in tree.centers() {
+                assert_eq!(farthest[c], min);
+            }
+            let rooted = tree.rooted(0);
+            assert_eq!(rooted.dist, all_dists[0]);
+            assert_eq!(rooted.subtree_size[0], n);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "graph must be undirected")]
+    fn test_directed_graph() {
+        let graph = Graph::from_atcoder_tuples(3, &[(1, 2), (2, 3)], true);
+        graph.diameter();
+    }
+
+    fn random_tree(n: usize, rng: &mut Rng) -> Vec<Vec<usize>> {
+        random_weighted_tree(n, rng)
+            .into_iter()
+            .map(|edges| edges.into_iter().map(|e| e.to).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_isomorphism_with_permutations() {
+        let mut rng = Rng::new(71);
+        let mut isomorphism = TreeIsomorphism::new();
+        for n in 1..8 {
+            let trees: Vec<Vec<Vec<usize>>> = (0..6).map(|_| random_tree(n, &mut rng)).collect();
+            for a in &trees {
+                for b in &trees {
+                    // a and b are isomorphic if some relabeling of a gives b
+                    let edges = |t: &Vec<Vec<usize>>, p: &[usize]| {
+                        (0..n)
+                            .flat_map(|v| t[v].iter().map(move |&to| (p[v], p[to])))
+                            .sorted()
+                            .collect::<Vec<_>>()
+                    };
+                    let identity: Vec<usize> = (0..n).collect();
+                    let b_edges = edges(b, &identity);
+                    let unrooted = (0..n).permutations(n).any(|p| edges(a, &p) == b_edges);
+                    assert_eq!(
+                        isomorphism.unrooted_id(a) == isomorphism.unrooted_id(b),
+                        unrooted
+                    );
+                    let rooted = (0..n)
+                        .permutations(n)
+                        .any(|p| p[0] == 0 && edges(a, &p) == b_edges);
+                    assert_eq!(
+                        isomorphism.rooted_id(a, 0) == isomorphism.rooted_id(b, 0),
+                        rooted
+                    );
+                }
+            }
+        }
+    }
+}
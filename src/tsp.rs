@@ -0,0 +1,242 @@
+use crate::bitset::Bitset;
+
+/// Order of visiting vertexes and its total cost
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tour {
+    pub cost: usize,
+    /// Visited vertexes in order. A closed tour ends with its first vertex again.
+    pub path: Vec<usize>,
+}
+
+/// dp\[mask\]\[v\]: minimum cost to visit exactly the vertexes of mask, starting from one of starts and ending at v
+fn bit_dp(dist: &[Vec<Option<usize>>], starts: &[usize]) -> Vec<Vec<Option<usize>>> {
+    let n = dist.len();
+    let mut dp: Vec<Vec<Option<usize>>> = vec![vec![None; n]; 1 << n];
+    for &s in starts {
+        dp[0.turned_bit_on(s)][s] = Some(0);
+    }
+    for mask in 1..1usize << n {
+        for v in 0..n {
+            let Some(cost) = dp[mask][v] else { continue };
+            for to in 0..n {
+                if mask.is_bit_on(to) {
+                    continue;
+                }
+                let Some(d) = dist[v][to] else { continue };
+                let next = &mut dp[mask.turned_bit_on(to)][to];
+                if next.is_none_or(|next_cost| cost + d < next_cost) {
+                    *next = Some(cost + d);
+                }
+            }
+        }
+    }
+    dp
+}
+
+/// Trace back the path visiting all vertexes of mask and ending at last
+fn restore_path(
+    dist: &[Vec<Option<usize>>],
+    dp: &[Vec<Option<usize>>],
+    mut mask: usize,
+    mut last: usize,
+) -> Vec<usize> {
+    let n = dist.len();
+    let mut path = vec![last];
+    while mask.count_ones() > 1 {
+        let cost = dp[mask][last].unwrap();
+        let prev_mask = mask.turned_bit_off(last);
+        let prev = (0..n)
+            .find(|&u| {
+                prev_mask.is_bit_on(u)
+                    && dp[prev_mask][u].zip(dist[u][last]).map(|(c, d)| c + d) == Some(cost)
+            })
+            .unwrap();
+        path.push(prev);
+        mask = prev_mask;
+        last = prev;
+    }
+    path.reverse();
+    path
+}
+
+/// Shortest path visiting every vertex exactly once in O(2^n n^2),
+/// where dist\[u\]\[v\] is the cost from u to v or None if there is no edge.
+/// The path starts at start if given, otherwise at any vertex.
+/// Returns None if there is no Hamiltonian path.
+/// ```
+/// use competitive_tools_rust::tsp::{shortest_hamiltonian_path, Tour};
+/// // 0 -1- 1 -2- 2, 0 -5- 2
+/// let dist = vec![
+///     vec![Some(0), Some(1), Some(5)],
+///     vec![Some(1), Some(0), Some(2)],
+///     vec![Some(5), Some(2), Some(0)],
+/// ];
+/// assert_eq!(
+///     shortest_hamiltonian_path(&dist, Some(1)),
+///     Some(Tour { cost: 6, path: vec![1, 0, 2] })
+/// );
+/// assert_eq!(
+///     shortest_hamiltonian_path(&dist, None),
+///     Some(Tour { cost: 3, path: vec![2, 1, 0] })
+/// );
+/// let star = vec![
+///     vec![Some(0), Some(1), Some(1)],
+///     vec![Some(1), Some(0), None],
+///     vec![Some(1), None, Some(0)],
+/// ];
+/// assert_eq!(shortest_hamiltonian_path(&star, Some(0)), None);
+/// ```
+pub fn shortest_hamiltonian_path(
+    dist: &[Vec<Option<usize>>],
+    start: Option<usize>,
+) -> Option<Tour> {
+    let n = dist.len();
+    if n == 0 {
+        return Some(Tour {
+            cost: 0,
+            path: vec![],
+        });
+    }
+    let starts: Vec<usize> = match start {
+        Some(s) => vec![s],
+        None => (0..n).collect(),
+    };
+    let dp = bit_dp(dist, &starts);
+    let full = (1 << n) - 1;
+    let (cost, last) = (0..n)
+        .filter_map(|v| dp[full][v].map(|cost| (cost, v)))
+        .min()?;
+    Some(Tour {
+        cost,
+        path: restore_path(dist, &dp, full, last),
+    })
+}
+
+/// Shortest cycle visiting every vertex exactly once (traveling salesman) in O(2^n n^2),
+/// where dist\[u\]\[v\] is the cost from u to v or None if there is no edge.
+/// The tour starts and ends at 0. Returns None if there is no Hamiltonian cycle.
+/// A single vertex is a tour \[0, 0\] of cost 0, regardless of dist\[0\]\[0\].
+/// ```
+/// use competitive_tools_rust::tsp::{shortest_hamiltonian_cycle, Tour};
+/// // directed: 0 -> 1 -> 2 -> 3 -> 0 costs 4, and the reverse costs 8
+/// let dist = vec![
+///     vec![None, Some(1), None, Some(2)],
+///     vec![Some(2), None, Some(1), None],
+///     vec![None, Some(2), None, Some(1)],
+///     vec![Some(1), None, Some(2), None],
+/// ];
+/// assert_eq!(
+///     shortest_hamiltonian_cycle(&dist),
+///     Some(Tour { cost: 4, path: vec![0, 1, 2, 3, 0] })
+/// );
+/// ```
+pub fn shortest_hamiltonian_cycle(dist: &[Vec<Option<usize>>]) -> Option<Tour> {
+    let n = dist.len();
+    if n == 0 {
+        return Some(Tour {
+            cost: 0,
+            path: vec![],
+        });
+    }
+    if n == 1 {
+        return Some(Tour {
+            cost: 0,
+            path: vec![0, 0],
+        });
+    }
+    let dp = bit_dp(dist, &[0]);
+    let full = (1 << n) - 1;
+    let (cost, last) = (0..n)
+        .filter_map(|v| match (dp[full][v], dist[v][0]) {
+            (Some(cost), Some(d)) => Some((cost + d, v)),
+            _ => None,
+        })
+        .min()?;
+    let mut path = restore_path(dist, &dp, full, last);
+    path.push(0);
+    Some(Tour { cost, path })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::Rng;
+    use crate::tsp::{Tour, shortest_hamiltonian_cycle, shortest_hamiltonian_path};
+    use itertools::Itertools;
+
+    fn path_cost(dist: &[Vec<Option<usize>>], path: &[usize]) -> Option<usize> {
+        path.windows(2).map(|w| dist[w[0]][w[1]]).sum()
+    }
+
+    #[test]
+    fn test_with_permutations() {
+        let mut rng = Rng::new(43);
+        for _ in 0..100 {
+            let n = 1 + rng.rand(6);
+            let dist: Vec<Vec<Option<usize>>> = (0..n)
+                .map(|_| {
+                    (0..n)
+                        .map(|_| {
+                            if rng.rand(4) == 0 {
+                                None
+                            } else {
+                                Some(rng.rand(10))
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let open = (0..n)
+                .permutations(n)
+                .filter_map(|p| path_cost(&dist, &p))
+                .min();
+            let result = shortest_hamiltonian_path(&dist, None);
+            assert_eq!(result.as_ref().map(|tour| tour.cost), open);
+            if let Some(tour) = result {
+                assert_eq!(path_cost(&dist, &tour.path), Some(tour.cost));
+                assert!(tour.path.iter().copied().sorted().eq(0..n));
+            }
+
+            let from_zero = (0..n)
+                .permutations(n)
+                .filter(|p| p[0] == 0)
+                .filter_map(|p| path_cost(&dist, &p))
+                .min();
+            let result = shortest_hamiltonian_path(&dist, Some(0));
+            assert_eq!(result.as_ref().map(|tour| tour.cost), from_zero);
+
+            if n == 1 {
+                continue;
+            }
+            let closed = (0..n)
+                .permutations(n)
+                .filter(|p| p[0] == 0)
+                .filter_map(|mut p| {
+                    p.push(0);
+                    path_cost(&dist, &p)
+                })
+                .min();
+            let result = shortest_hamiltonian_cycle(&dist);
+            assert_eq!(result.as_ref().map(|tour| tour.cost), closed);
+            if let Some(tour) = result {
+                assert_eq!(path_cost(&dist, &tour.path), Some(tour.cost));
+                assert_eq!(tour.path.len(), n + 1);
+                assert_eq!(tour.path[0], 0);
+                assert_eq!(tour.path[n], 0);
+            }
+        }
+    }
+    #[test]
+    fn test_single_vertex_cycle() {
+        let tour = Tour {
+            cost: 0,
+            path: vec![0, 0],
+        };
+        for self_loop in [None, Some(0), Some(7)] {
+            assert_eq!(
+                shortest_hamiltonian_cycle(&[vec![self_loop]]),
+                Some(tour.clone())
+            );
+        }
+    }
+}
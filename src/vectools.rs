@@ -48,7 +48,7 @@ mod tests {
 
     #[test]
     fn test() {
-        let strs = vec![
+        let strs = [
             "ab".to_string(),
             "bc".to_string(),
             "ab".to_string(),